@@ -0,0 +1,141 @@
+import { FileGraph, ImportEdge } from "./types";
+
+export interface BarrelCollapse {
+  files: FileGraph[];
+  /** Paths of the barrel files that were removed. */
+  barrels: string[];
+  /** Imported names that could not be traced through a barrel and were dropped. */
+  dropped: number;
+}
+
+/**
+ * A file that declares nothing and only re-exports other modules, such as an `index.ts` made of
+ * `export * from` and `export { X } from` statements.
+ */
+export function isBarrelFile(file: FileGraph): boolean {
+  return file.symbols.length === 0 && file.imports.length > 0 && file.imports.every((edge) => edge.kind === "re-export");
+}
+
+function bareName(entry: string): string {
+  return entry.replace(/^type\s+/, "");
+}
+
+/** Name an import or re-export entry takes from its module: `A` for `A as B`. */
+function sourceName(entry: string): string {
+  return bareName(entry).split(/\s+as\s+/)[0];
+}
+
+/** Name an entry is visible under afterwards: `B` for `A as B`. */
+function localName(entry: string): string {
+  const parts = bareName(entry).split(/\s+as\s+/);
+  return parts[parts.length - 1];
+}
+
+interface BarrelIndex {
+  files: Map<string, FileGraph>;
+  barrels: Set<string>;
+}
+
+/**
+ * Files that define `name` as exported by `path`, following re-exports through nested barrels and through
+ * files that both declare symbols and re-export others. When a re-export names `name` but its module does
+ * not show where it is defined, as with `export { default as X } from "./x"` over `export default {...}`,
+ * the re-exported module itself is the answer. Empty when `name` cannot be traced to a project file.
+ */
+function definitionsOf(index: BarrelIndex, path: string, name: string, seen = new Set<string>()): string[] {
+  const file = index.files.get(path);
+  const key = `${path}#${name}`;
+  if (!file || seen.has(key)) {
+    return [];
+  }
+  seen.add(key);
+
+  if (file.symbols.some((symbol) => symbol.exported && (symbol.name === name || symbol.exportedAs?.includes(name)))) {
+    return [path];
+  }
+
+  for (const edge of file.imports) {
+    if (edge.kind !== "re-export" || !edge.resolved) {
+      continue;
+    }
+
+    for (const entry of edge.symbols) {
+      if (entry === "*") {
+        // `export *` never re-exports a module's default export.
+        const found = name === "default" ? [] : definitionsOf(index, edge.resolved, name, seen);
+        if (found.length > 0) {
+          return found;
+        }
+      } else if (localName(entry) === name) {
+        const found = entry.startsWith("* as ") ? [] : definitionsOf(index, edge.resolved, sourceName(entry), seen);
+        return found.length > 0 ? found : modulesBehind(index, edge.resolved);
+      }
+    }
+  }
+
+  return [];
+}
+
+/** Non-barrel files a barrel re-exports from, directly or through nested barrels. */
+function modulesBehind(index: BarrelIndex, path: string, seen = new Set<string>()): string[] {
+  if (!index.files.has(path)) {
+    return [];
+  }
+
+  if (!index.barrels.has(path)) {
+    return [path];
+  }
+
+  if (seen.has(path)) {
+    return [];
+  }
+  seen.add(path);
+
+  const targets = (index.files.get(path)?.imports ?? []).flatMap((edge) => (edge.resolved ? modulesBehind(index, edge.resolved, seen) : []));
+  return Array.from(new Set(targets));
+}
+
+/**
+ * Removes barrel files and points every import of a barrel at the files that define the imported names.
+ * An import whose names come from several files is split into one edge per file; namespace and
+ * side-effect imports of a barrel fan out to every file behind it. The original `from` specifier is kept.
+ * A name the barrel re-exports explicitly keeps pointing at the re-exported module when its definition
+ * cannot be found there; only names whose module is outside the project, such as re-exports of external
+ * packages, are dropped.
+ */
+export function collapseBarrels(files: FileGraph[]): BarrelCollapse {
+  const barrels = new Set(files.filter(isBarrelFile).map((file) => file.path));
+  if (barrels.size === 0) {
+    return { files, barrels: [], dropped: 0 };
+  }
+
+  const index: BarrelIndex = { files: new Map(files.map((file) => [file.path, file])), barrels };
+  let dropped = 0;
+
+  const rewrite = (edge: ImportEdge): ImportEdge[] => {
+    if (!edge.resolved || !barrels.has(edge.resolved)) {
+      return [edge];
+    }
+
+    const named = edge.symbols.filter((entry) => !entry.startsWith("* as "));
+    if (named.length < edge.symbols.length || edge.symbols.length === 0) {
+      const targets = modulesBehind(index, edge.resolved);
+      dropped += targets.length === 0 ? Math.max(edge.symbols.length, 1) : 0;
+      return targets.map((target) => ({ ...edge, resolved: target }));
+    }
+
+    const byTarget = new Map<string, string[]>();
+    for (const entry of named) {
+      const targets = definitionsOf(index, edge.resolved, sourceName(entry));
+      dropped += targets.length === 0 ? 1 : 0;
+      for (const target of targets) {
+        byTarget.set(target, [...(byTarget.get(target) ?? []), entry]);
+      }
+    }
+
+    return Array.from(byTarget, ([target, symbols]) => ({ ...edge, symbols, resolved: target }));
+  };
+
+  const collapsed = files.filter((file) => !barrels.has(file.path)).map((file) => ({ ...file, imports: file.imports.flatMap(rewrite) }));
+  return { files: collapsed, barrels: Array.from(barrels).sort(), dropped };
+}
@@ -0,0 +1,83 @@
+import { estimateTokens } from "./estimate";
+import { CodeGraph, FileGraph, SymbolKind, SymbolNode } from "./types";
+import { serializeGraph, SerializeOptions } from "./yamlSerializer";
+
+export interface BudgetResult {
+  graph: CodeGraph;
+  options: SerializeOptions;
+  /** Estimated tokens of the final serialization. */
+  tokens: number;
+  /** Names of the degradation steps that were applied, in order. */
+  steps: string[];
+}
+
+export class TokenBudgetError extends Error {
+  readonly tokens: number;
+  readonly budget: number;
+
+  constructor(tokens: number, budget: number, steps: string[]) {
+    super(`The most compact graph is ~${tokens} tokens, over the budget of ${budget} (applied: ${steps.join(", ")})`);
+    this.name = "TokenBudgetError";
+    this.tokens = tokens;
+    this.budget = budget;
+  }
+}
+
+interface DegradationStep {
+  name: string;
+  apply(graph: CodeGraph, options: SerializeOptions): { graph: CodeGraph; options: SerializeOptions };
+}
+
+/** Kinds dropped first: they say the least about the structure of a codebase. */
+const LOW_VALUE_KINDS: SymbolKind[] = ["variable", "interface", "type"];
+
+function mapSymbols(graph: CodeGraph, map: (symbols: SymbolNode[]) => SymbolNode[]): CodeGraph {
+  return { ...graph, files: graph.files.map((file): FileGraph => ({ ...file, symbols: map(file.symbols) })) };
+}
+
+function withoutField(symbol: SymbolNode, field: keyof SymbolNode): SymbolNode {
+  const { [field]: _removed, ...rest } = symbol;
+  return rest as SymbolNode;
+}
+
+const DEGRADATION_STEPS: DegradationStep[] = [
+  { name: "short-keys", apply: (graph, options) => ({ graph, options: { ...options, shortKeys: true, metadata: false } }) },
+  { name: "no-docs", apply: (graph, options) => ({ graph: mapSymbols(graph, (symbols) => symbols.map((symbol) => withoutField(withoutField(symbol, "doc"), "summary"))), options }) },
+  {
+    name: "no-low-value-kinds",
+    apply: (graph, options) => ({ graph: mapSymbols(graph, (symbols) => symbols.filter((symbol) => !LOW_VALUE_KINDS.includes(symbol.kind))), options }),
+  },
+  {
+    name: "no-signatures",
+    apply: (graph, options) => ({ graph: mapSymbols(graph, (symbols) => symbols.map((symbol) => withoutField(withoutField(symbol, "signature"), "members"))), options }),
+  },
+  { name: "unexported-dropped", apply: (graph, options) => ({ graph: mapSymbols(graph, (symbols) => symbols.filter((symbol) => symbol.exported)), options }) },
+];
+
+/**
+ * Degrades the graph one step at a time until its serialization fits `maxTokens`: short keys, then no
+ * docs or summaries, then no variables, interfaces or types, then no signatures or member lists, then
+ * exported symbols only. Classes and functions are kept the longest. Throws a TokenBudgetError when even
+ * the last step does not fit.
+ */
+export function fitToTokenBudget(graph: CodeGraph, maxTokens: number, options: SerializeOptions = {}): BudgetResult {
+  let current = { graph, options };
+  let tokens = estimateTokens(serializeGraph(graph, options));
+  const steps: string[] = [];
+
+  for (const step of DEGRADATION_STEPS) {
+    if (tokens <= maxTokens) {
+      break;
+    }
+
+    current = step.apply(current.graph, current.options);
+    tokens = estimateTokens(serializeGraph(current.graph, current.options));
+    steps.push(step.name);
+  }
+
+  if (tokens > maxTokens) {
+    throw new TokenBudgetError(tokens, maxTokens, steps);
+  }
+
+  return { ...current, tokens, steps };
+}
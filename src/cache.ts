@@ -0,0 +1,103 @@
+import crypto from "crypto";
+import fs from "fs";
+import path from "path";
+import ts from "typescript";
+import { ParserOptions } from "./parser";
+import { FileGraph } from "./types";
+
+const CACHE_FORMAT = 4;
+const MANIFEST_FILE = "ycg-cache.json";
+
+interface CacheEntry {
+  hash: string;
+  file: FileGraph;
+}
+
+interface CacheManifest {
+  format: number;
+  key: string;
+  entries: Record<string, CacheEntry>;
+}
+
+export interface ParseCache {
+  /** Returns the cached parse of `filePath` when its content hash is unchanged. */
+  lookup(filePath: string, hash: string): FileGraph | undefined;
+  store(filePath: string, hash: string, file: FileGraph): void;
+  /** Writes the entries seen during this run; files that no longer exist are dropped. */
+  save(): void;
+  readonly hits: number;
+  readonly misses: number;
+}
+
+export function hashContent(content: string): string {
+  return crypto.createHash("sha1").update(content).digest("hex");
+}
+
+/** Everything besides file content that changes what the parser produces. */
+function cacheKey(options: ParserOptions): string {
+  return hashContent(
+    JSON.stringify({
+      format: CACHE_FORMAT,
+      typescript: ts.version,
+      classSummaries: options.classSummaries,
+      complexity: options.complexity,
+      ranges: options.ranges,
+      docPolicy: options.docPolicy,
+      docs: options.docs,
+      signatures: options.signatures,
+      implementations: options.implementations,
+      members: options.members,
+    })
+  );
+}
+
+function readManifest(manifestPath: string, key: string): Record<string, CacheEntry> {
+  try {
+    const manifest = JSON.parse(fs.readFileSync(manifestPath, "utf8")) as CacheManifest;
+    return manifest.format === CACHE_FORMAT && manifest.key === key ? manifest.entries : {};
+  } catch {
+    return {};
+  }
+}
+
+/**
+ * Opens the per-file parse cache stored in `directory`. The whole cache is discarded when parser options
+ * or the TypeScript version differ from the run that wrote it. Import resolution and filtering are never
+ * cached, so cross-file edges are rebuilt on every run.
+ */
+export function openParseCache(directory: string, options: ParserOptions = {}): ParseCache {
+  const manifestPath = path.join(directory, MANIFEST_FILE);
+  const key = cacheKey(options);
+  const previous = readManifest(manifestPath, key);
+  const current: Record<string, CacheEntry> = {};
+  let hits = 0;
+  let misses = 0;
+
+  return {
+    lookup(filePath, hash) {
+      const entry = previous[filePath];
+      if (entry?.hash === hash) {
+        hits += 1;
+        current[filePath] = entry;
+        return entry.file;
+      }
+
+      misses += 1;
+      return undefined;
+    },
+    store(filePath, hash, file) {
+      current[filePath] = { hash, file };
+    },
+    save() {
+      const manifest: CacheManifest = { format: CACHE_FORMAT, key, entries: current };
+      fs.mkdirSync(directory, { recursive: true });
+      fs.writeFileSync(manifestPath, JSON.stringify(manifest), "utf8");
+    },
+    get hits() {
+      return hits;
+    },
+    get misses() {
+      return misses;
+    },
+  };
+}
@@ -0,0 +1,67 @@
+import path from "path";
+import YAML from "yaml";
+import { estimateTokens } from "./estimate";
+import { CodeGraph } from "./types";
+import { serializeGraph, SerializeOptions } from "./yamlSerializer";
+
+export interface GraphChunk {
+  graph: CodeGraph;
+  /** Estimated tokens of the serialized chunk. */
+  tokens: number;
+}
+
+/**
+ * Splits a graph into chunks whose serialization stays under about `maxTokens` tokens each. Files are
+ * kept whole and in path order, so files of the same directory tend to share a chunk; a single file
+ * larger than the budget gets a chunk of its own. Imports keep their `resolved` paths, which may point
+ * into other chunks.
+ */
+export function chunkGraph(graph: CodeGraph, maxTokens: number, options: SerializeOptions = {}): GraphChunk[] {
+  const envelope = estimateTokens(serializeGraph({ ...graph, files: [] }, options));
+  const chunks: GraphChunk[] = [];
+  let files: CodeGraph["files"] = [];
+  let tokens = envelope;
+
+  for (const file of graph.files) {
+    const fileTokens = estimateTokens(serializeGraph({ ...graph, files: [file] }, options)) - envelope;
+    if (files.length > 0 && tokens + fileTokens > maxTokens) {
+      chunks.push({ graph: { ...graph, files }, tokens });
+      files = [];
+      tokens = envelope;
+    }
+
+    files.push(file);
+    tokens += fileTokens;
+  }
+
+  if (files.length > 0 || chunks.length === 0) {
+    chunks.push({ graph: { ...graph, files }, tokens });
+  }
+
+  return chunks;
+}
+
+/** `graph.yaml` → `graph.part1.yaml`, `graph.part2.yaml`, ... */
+export function chunkPath(outputPath: string, index: number): string {
+  const extension = path.extname(outputPath);
+  return `${outputPath.slice(0, outputPath.length - extension.length)}.part${index + 1}${extension}`;
+}
+
+export function manifestPath(outputPath: string): string {
+  const extension = path.extname(outputPath);
+  return `${outputPath.slice(0, outputPath.length - extension.length)}.manifest${extension || ".yaml"}`;
+}
+
+/**
+ * Lists every chunk with its file name, estimated tokens and the graph files it holds, so a retrieval
+ * pipeline can find the chunk for a path without opening them all.
+ */
+export function renderChunkManifest(chunks: GraphChunk[], outputPath: string): string {
+  return YAML.stringify({
+    chunks: chunks.map((chunk, index) => ({
+      file: path.basename(chunkPath(outputPath, index)),
+      tokens: chunk.tokens,
+      files: chunk.graph.files.map((file) => file.path),
+    })),
+  });
+}
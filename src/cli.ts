@@ -1,30 +1,182 @@
 #!/usr/bin/env node
 import fs from "fs";
 import path from "path";
-import { buildGraph } from "./graph";
-import { serializeGraph } from "./yamlSerializer";
+import readline from "readline/promises";
+import { fitToTokenBudget, TokenBudgetError } from "./budget";
+import { openParseCache } from "./cache";
+import { chunkGraph, chunkPath, manifestPath, renderChunkManifest } from "./chunks";
+import { ConfigError, DEFAULT_CONFIG_FILE, loadConfigFile, validateConfig, ValidatedConfig, YcgConfig } from "./config";
+import { Diagnostic, DiagnosticCodes, formatDiagnostic } from "./diagnostics";
+import { DeclarationMerge } from "./declarations";
+import { DEFAULT_RENAME_THRESHOLD, diffGraphs, renderGraphDiff } from "./diff";
+import { DoctorCheck, runDoctor } from "./doctor";
+import { IMPORT_EDGE_KINDS } from "./edges";
+import { estimateGraph, estimateTokens, GraphEstimate } from "./estimate";
+import { buildGraph, findDegradedFiles, findUnresolvedImports, parseHealth, Removal } from "./graph";
+import { applyOutputFilters, checkOutputTarget, OUTPUT_FORMATS, OutputFormat, OutputTarget, parseEmitSpec, streamOutput, writeFileAtomic } from "./outputs";
+import { CiProvider, ciSnippetPath, detectProject, proposeConfig, renderCiSnippet, renderConfigFile } from "./init";
+import { matchSymbols, QueryDirection, querySubgraph } from "./query";
+import { NODE_ROLES } from "./roles";
+import { embedRunMetadata } from "./run";
+import { GRAPH_SCHEMA_VERSION, isNewerSchema } from "./schema";
+import { computeGraphStats, computeTokenReport, GraphStats, TokenReport } from "./stats";
+import { StyleConfig } from "./styles";
+import { CLASS_SUMMARY_MODES } from "./summaries";
+import { TEST_FILE_MODES } from "./testFiles";
+import { CodeGraph } from "./types";
+import { GraphProblem, SYMBOL_KINDS, validateGraph } from "./validate";
+import { createWatchLoop, watchSourceTree } from "./watch";
+import { GRAPH_SECTIONS, GraphDocument, readGraphDocument, SerializationError } from "./yamlSerializer";
 
-interface CliOptions {
+interface CliOptions extends YcgConfig {
   root: string;
   out: string;
-  extensions?: string[];
+  config?: string;
+  emit?: string[];
+  cacheDir?: string;
+  maxTokens?: number;
+  chunkTokens?: number;
+  validate?: boolean;
+  embedMetadata?: boolean;
+  styles?: StyleConfig;
+  dryRun?: boolean;
+  explainFiltering?: boolean;
+  json?: boolean;
+  watch?: boolean;
+  watchDebounce?: number;
+  messageFormat?: "human" | "json";
+  allow?: string[];
+  warnings?: boolean;
+  failOnWarnings?: boolean;
+  quiet?: boolean;
+  verbose?: boolean;
   help?: boolean;
 }
 
+interface InitOptions {
+  root: string;
+  yes?: boolean;
+  force?: boolean;
+  ci?: CiProvider;
+}
+
 function printHelp(): void {
   console.log(`YAML Code Graph (TypeScript)
 Usage: ycg [options]
+       ycg init [--root <path>] [--yes] [--force] [--ci github|gitlab]
+       ycg doctor [--json]
+       ycg stats [options] [--token-report] [--json]
+       ycg validate <graph.yaml> [--json]
+       ycg diff <before.yaml> <after.yaml> [--format text|yaml|json] [--rename-threshold <r>] [--exit-code]
+       ycg query <graph.yaml> --symbol <selector> [--depth <n>] [--direction out|in|both] [--format <f>] [--out <file>]
+       ycg migrate <graph.yaml> [--to-version <n>] [--out <file>]
 
 Options:
-  -r, --root <path>         Root directory to scan (default: .)
-  -o, --out <file>          Output YAML file (default: graph.yaml)
-  -e, --extensions <list>   Comma-separated list of extensions to include (default: .ts,.tsx,.js,.jsx,.mjs,.cjs)
-  -h, --help                Show this help message
+  -r, --root <path>           Root directory to scan (default: .)
+  -o, --out <file>            Output file, or - for stdout (default: graph.yaml)
+  -c, --config <file>         Configuration file (default: ycg.config.json when present)
+  -e, --extensions <list>     Comma-separated list of extensions to include (default: .ts,.tsx,.js,.jsx,.mjs,.cjs)
+      --tests <mode>          Test files (*.spec.*, *.test.*, __tests__/, test/, tests/): include, exclude, or tag
+  -f, --format <f>            Output format: yaml, html, dot or graphml (default: yaml)
+      --emit <file[:opts]>    Write an additional output with its own options (short-keys, no-metadata, a format, or filters such as public-only, focus=<glob>); repeatable
+      --max-tokens <n>        Drop detail from YAML outputs until they fit about n tokens
+      --chunk-tokens <n>      Split YAML outputs into parts of about n tokens plus a manifest
+      --short-keys            Serialize with short field names to reduce output size
+      --no-metadata           Omit root and generatedAt from the output
+      --embed-metadata        Record tool version, options and counts under run
+      --sections <list>       Sections to write: symbols, imports, dependencies, meta (default: all)
+      --min-parse-health <r>  Fail when the share of cleanly parsed files is below r (0-1)
+      --class-summaries <m>   Add a one-sentence summary to classes: heuristic, doc-only or off (default: off)
+      --tsconfig <file>       tsconfig.json used to resolve path aliases (default: <root>/tsconfig.json)
+      --focus-path <glob>     Keep only matching files and their import neighborhood (repeatable)
+      --depth <n>             Import hops to expand around --focus-path (default: 1)
+      --keep-role <roles>     Keep only symbols with these React roles: component, hook, hoc
+      --external-noise-threshold <n>
+                              Summarize externals imported by more than n files instead of listing their edges
+      --public-only           Keep only exported symbols
+      --include-kinds <kinds> Keep only symbols of these kinds: function, class, interface, type, enum,
+                              namespace, variable
+      --exclude-kinds <kinds> Drop symbols of these kinds
+      --exclude-imports <kinds>
+                              Drop imports of these kinds: value, type, side-effect, re-export
+      --drop-self-imports     Drop imports of a file by itself
+      --prune-orphans         Remove files left with no symbols, imports, or importers
+      --collapse-barrels      Remove re-export-only files and link their importers to the defining files
+      --packages              Record the workspace package (nearest package.json) of each file
+      --implementations       Link interfaces to the classes implementing them (implements / implementedBy)
+      --members               List the public methods and properties of classes and interfaces
+      --reverse-imports       List the importers of each file under importedBy
+      --file-dependencies     List the files each file imports under dependsOn
+      --complexity            Record branch count and line count on functions
+      --doc-max-chars <n>     Truncate symbol docs to n characters
+      --doc-first-sentence    Keep only the first sentence of symbol docs
+      --doc-strip-tags        Drop @param, @returns and other block tags from symbol docs
+      --no-docs               Skip doc comments entirely (faster on large trees)
+      --no-signatures         Skip symbol signatures entirely
+      --ranges                Record where each symbol's declaration ends
+      --keep-declarations     Keep .d.ts symbols that duplicate an implementation file
+      --cache-dir <dir>       Reuse parses of unchanged files from dir and update it
+      --no-validate           Skip the structural check of the graph before writing
+      --dry-run               Report what would be generated without writing any output
+      --explain-filtering     List every file and symbol left out of the graph and why, instead of writing it
+      --json                  With --explain-filtering, print the removals as JSON
+      --watch                 Stay running and regenerate the outputs whenever a source file changes
+      --watch-debounce <ms>   Quiet period after the last change before regenerating (default: 200)
+      --message-format <f>    Print warnings as human text or as one JSON object per line: human or json (default: human)
+      --allow <codes>         Comma-separated diagnostic codes to suppress, e.g. YCG001,YCG002
+      --warnings              List every unresolved import instead of a summary
+      --fail-on-warnings      Exit with code 1 when any warning was reported
+      --error-format <f>      Print a failure as human text or as a JSON object: human or json (default: human)
+  -q, --quiet                 Do not print progress messages
+  -v, --verbose               Also print file, symbol, import and token counts
+  -h, --help                  Show this help message
 `);
 }
 
-function parseArgs(argv: string[]): CliOptions {
-  const options: CliOptions = { root: ".", out: "graph.yaml" };
+/**
+ * Failure categories, each with its own exit code so scripts can tell a bad invocation from a bad input.
+ * Exit code 1 is kept for runs that completed but found problems: `--fail-on-warnings`, `ycg validate`
+ * errors, failed `ycg doctor` checks, and `ycg diff --exit-code` differences.
+ */
+type ErrorCategory = "config" | "input" | "validation" | "output" | "internal";
+
+const EXIT_CODES: Record<ErrorCategory, number> = { config: 2, input: 3, validation: 4, output: 5, internal: 10 };
+
+class CliError extends Error {
+  readonly category: ErrorCategory;
+  readonly path?: string;
+  readonly hint?: string;
+
+  constructor(category: ErrorCategory, message: string, details: { path?: string; hint?: string } = {}) {
+    super(message);
+    this.name = "CliError";
+    this.category = category;
+    this.path = details.path;
+    this.hint = details.hint;
+  }
+}
+
+let errorFormat: "human" | "json" = "human";
+
+function fail(message: string, category: ErrorCategory = "config", details: { path?: string; hint?: string } = {}): never {
+  if (errorFormat === "json") {
+    console.error(JSON.stringify({ category, exitCode: EXIT_CODES[category], message, ...details }));
+  } else {
+    console.error(details.hint ? `${message}\nHint: ${details.hint}` : message);
+  }
+  process.exit(EXIT_CODES[category]);
+}
+
+function parseChoice<T extends string>(flag: string, value: string | undefined, choices: readonly T[]): T {
+  if (!value || !choices.includes(value as T)) {
+    fail(`Invalid value for ${flag}: ${value ?? "(missing)"} (expected one of ${choices.join(", ")})`);
+  }
+
+  return value as T;
+}
+
+function parseArgs(argv: string[]): Partial<CliOptions> {
+  const options: Partial<CliOptions> = {};
 
   for (let i = 0; i < argv.length; i += 1) {
     const arg = argv[i];
@@ -39,15 +191,198 @@ function parseArgs(argv: string[]): CliOptions {
         options.out = argv[i + 1] ?? options.out;
         i += 1;
         break;
+      case "-c":
+      case "--config":
+        options.config = argv[i + 1];
+        i += 1;
+        break;
       case "-e":
       case "--extensions": {
         const value = argv[i + 1];
         if (value) {
-          options.extensions = value.split(",").map((ext) => (ext.startsWith(".") ? ext : `.${ext}`));
+          options.extensions = value.split(",");
+        }
+        i += 1;
+        break;
+      }
+      case "-f":
+      case "--format":
+        options.format = parseChoice("--format", argv[i + 1], OUTPUT_FORMATS);
+        i += 1;
+        break;
+      case "--emit":
+        options.emit = [...(options.emit ?? []), argv[i + 1] ?? ""].filter(Boolean);
+        i += 1;
+        break;
+      case "--max-tokens":
+        options.maxTokens = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--chunk-tokens":
+        options.chunkTokens = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--short-keys":
+        options.shortKeys = true;
+        break;
+      case "--no-metadata":
+        options.metadata = false;
+        break;
+      case "--sections":
+        options.sections = (argv[i + 1] ?? "").split(",").map((section) => parseChoice(arg, section, GRAPH_SECTIONS));
+        i += 1;
+        break;
+      case "--embed-metadata":
+        options.embedMetadata = true;
+        break;
+      case "--min-parse-health":
+        options.minParseHealth = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--class-summaries":
+        options.classSummaries = parseChoice("--class-summaries", argv[i + 1], CLASS_SUMMARY_MODES);
+        i += 1;
+        break;
+      case "--tsconfig":
+        options.tsconfig = argv[i + 1];
+        i += 1;
+        break;
+      case "--focus-path":
+        options.focusPaths = [...(options.focusPaths ?? []), argv[i + 1] ?? ""].filter(Boolean);
+        i += 1;
+        break;
+      case "--depth":
+        options.focusDepth = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--keep-role":
+        options.keepRoles = (argv[i + 1] ?? "")
+          .split(",")
+          .map((role) => parseChoice("--keep-role", role, NODE_ROLES));
+        i += 1;
+        break;
+      case "--external-noise-threshold":
+        options.externalNoiseThreshold = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--public-only":
+        options.publicOnly = true;
+        break;
+      case "--include-kinds":
+      case "--exclude-kinds": {
+        const kinds = (argv[i + 1] ?? "")
+          .split(",")
+          .map((kind) => parseChoice(arg, kind, SYMBOL_KINDS));
+        if (arg === "--include-kinds") {
+          options.includeKinds = kinds;
+        } else {
+          options.excludeKinds = kinds;
         }
         i += 1;
         break;
       }
+      case "--exclude-imports":
+        options.excludeImports = (argv[i + 1] ?? "").split(",").map((kind) => parseChoice(arg, kind, IMPORT_EDGE_KINDS));
+        i += 1;
+        break;
+      case "--drop-self-imports":
+        options.dropSelfImports = true;
+        break;
+      case "--prune-orphans":
+        options.pruneOrphans = true;
+        break;
+      case "--tests":
+        options.tests = parseChoice("--tests", argv[i + 1], TEST_FILE_MODES);
+        i += 1;
+        break;
+      case "--collapse-barrels":
+        options.collapseBarrels = true;
+        break;
+      case "--packages":
+        options.packages = true;
+        break;
+      case "--implementations":
+        options.implementations = true;
+        break;
+      case "--members":
+        options.members = true;
+        break;
+      case "--reverse-imports":
+        options.reverseImports = true;
+        break;
+      case "--file-dependencies":
+        options.fileDependencies = true;
+        break;
+      case "--complexity":
+        options.complexity = true;
+        break;
+      case "--doc-max-chars":
+        options.docPolicy = { ...options.docPolicy, maxChars: Number(argv[i + 1]) };
+        i += 1;
+        break;
+      case "--doc-first-sentence":
+        options.docPolicy = { ...options.docPolicy, firstSentenceOnly: true };
+        break;
+      case "--doc-strip-tags":
+        options.docPolicy = { ...options.docPolicy, stripTags: true };
+        break;
+      case "--no-docs":
+        options.docs = false;
+        break;
+      case "--no-signatures":
+        options.signatures = false;
+        break;
+      case "--ranges":
+        options.ranges = true;
+        break;
+      case "--keep-declarations":
+        options.declarationFiles = "keep";
+        break;
+      case "--cache-dir":
+        options.cacheDir = argv[i + 1];
+        i += 1;
+        break;
+      case "--no-validate":
+        options.validate = false;
+        break;
+      case "--dry-run":
+        options.dryRun = true;
+        break;
+      case "--explain-filtering":
+        options.explainFiltering = true;
+        break;
+      case "--json":
+        options.json = true;
+        break;
+      case "--watch":
+        options.watch = true;
+        break;
+      case "--watch-debounce":
+        options.watchDebounce = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--message-format":
+        options.messageFormat = parseChoice("--message-format", argv[i + 1], ["human", "json"] as const);
+        i += 1;
+        break;
+      case "--allow":
+        options.allow = [...(options.allow ?? []), ...(argv[i + 1] ?? "").split(",").filter(Boolean)];
+        i += 1;
+        break;
+      case "--warnings":
+        options.warnings = true;
+        break;
+      case "--fail-on-warnings":
+        options.failOnWarnings = true;
+        break;
+      case "-q":
+      case "--quiet":
+        options.quiet = true;
+        break;
+      case "-v":
+      case "--verbose":
+        options.verbose = true;
+        break;
       case "-h":
       case "--help":
         options.help = true;
@@ -60,6 +395,58 @@ function parseArgs(argv: string[]): CliOptions {
   return options;
 }
 
+function parseInitArgs(argv: string[]): InitOptions {
+  const options: InitOptions = { root: "." };
+
+  for (let i = 0; i < argv.length; i += 1) {
+    const arg = argv[i];
+    switch (arg) {
+      case "-r":
+      case "--root":
+        options.root = argv[i + 1] ?? options.root;
+        i += 1;
+        break;
+      case "-y":
+      case "--yes":
+        options.yes = true;
+        break;
+      case "--force":
+        options.force = true;
+        break;
+      case "--ci":
+        options.ci = parseChoice("--ci", argv[i + 1], ["github", "gitlab"] as const);
+        i += 1;
+        break;
+      default:
+        break;
+    }
+  }
+
+  return options;
+}
+
+function withConfigErrors<T>(action: () => T): T {
+  try {
+    return action();
+  } catch (error) {
+    if (error instanceof ConfigError) {
+      fail(error.message, "config", { hint: error.suggestion });
+    }
+    throw error;
+  }
+}
+
+function resolveOptions(flags: Partial<CliOptions>): CliOptions {
+  const configPath = flags.config ?? (fs.existsSync(DEFAULT_CONFIG_FILE) ? DEFAULT_CONFIG_FILE : undefined);
+  const fileConfig = configPath ? withConfigErrors(() => loadConfigFile(configPath)) : {};
+  const explicitFlags = Object.fromEntries(Object.entries(flags).filter(([, value]) => value !== undefined));
+  return { root: ".", out: "graph.yaml", ...fileConfig, ...explicitFlags };
+}
+
+function loadConfig(options: CliOptions): ValidatedConfig {
+  return withConfigErrors(() => validateConfig(options));
+}
+
 function ensureDirectoryExists(filePath: string): void {
   const directory = path.dirname(filePath);
   if (!fs.existsSync(directory)) {
@@ -67,21 +454,777 @@ function ensureDirectoryExists(filePath: string): void {
   }
 }
 
-function run(): void {
-  const args = parseArgs(process.argv.slice(2));
+function createReporter(options: CliOptions, onReported?: (diagnostic: Diagnostic) => void): (diagnostic: Diagnostic) => void {
+  const allowed = new Set(options.allow ?? []);
+  return (diagnostic) => {
+    if (allowed.has(diagnostic.code)) {
+      return;
+    }
+
+    onReported?.(diagnostic);
+    console.error(options.messageFormat === "json" ? JSON.stringify(diagnostic) : formatDiagnostic(diagnostic));
+  };
+}
+
+function reportParseHealth(graph: CodeGraph, options: CliOptions, report: (diagnostic: Diagnostic) => void): void {
+  const degraded = findDegradedFiles(graph);
+  const hint = "if the syntax is valid, update ycg to pick up a newer TypeScript parser";
+
+  if (options.messageFormat === "json") {
+    for (const file of degraded) {
+      report({
+        level: "warning",
+        code: DiagnosticCodes.parseErrors,
+        file: file.path,
+        message: `parsed with ${file.parseErrors} syntax error(s); symbols may be missing`,
+        hint,
+      });
+    }
+  } else if (degraded.length > 0) {
+    const preview = degraded.slice(0, 3).map((file) => file.path).join(", ");
+    const more = degraded.length > 3 ? `, and ${degraded.length - 3} more` : "";
+    report({
+      level: "warning",
+      code: DiagnosticCodes.parseErrors,
+      message: `${degraded.length} file(s) parsed with syntax errors (${preview}${more}); symbols may be missing`,
+      hint,
+    });
+  }
+
+  const health = parseHealth(graph);
+  if (options.minParseHealth !== undefined && health < options.minParseHealth) {
+    throw new CliError("validation", `Parse health ${health.toFixed(2)} is below the required ${options.minParseHealth}`);
+  }
+}
+
+/** Progress goes to stderr so that `--out -` leaves stdout with nothing but the graph. */
+function progress(options: CliOptions, message: string): void {
+  if (!options.quiet) {
+    console.error(message);
+  }
+}
+
+function reportUnresolvedImports(graph: CodeGraph, options: CliOptions, report: (diagnostic: Diagnostic) => void): void {
+  const unresolved = findUnresolvedImports(graph);
+  const hint = "check the path, or whether the target is excluded by --extensions or .ycgignore";
+
+  if (options.messageFormat === "json" || options.warnings) {
+    for (const entry of unresolved) {
+      report({
+        level: "warning",
+        code: DiagnosticCodes.unresolvedImport,
+        file: entry.file,
+        message: `import "${entry.specifier}" does not match any file in the graph`,
+        hint,
+      });
+    }
+  } else if (unresolved.length > 0) {
+    const files = new Set(unresolved.map((entry) => entry.file)).size;
+    report({
+      level: "warning",
+      code: DiagnosticCodes.unresolvedImport,
+      message: `${unresolved.length} relative import(s) in ${files} file(s) do not match any file in the graph (use --warnings to list them)`,
+      hint,
+    });
+  }
+}
+
+const REMOVAL_EXAMPLES = 10;
+
+function printRemovals(removals: Removal[]): void {
+  if (removals.length === 0) {
+    console.log("Nothing was filtered out.");
+    return;
+  }
+
+  const groups = new Map<string, Removal[]>();
+  for (const removal of removals) {
+    const key = `${removal.stage}: ${removal.rule}`;
+    groups.set(key, [...(groups.get(key) ?? []), removal]);
+  }
+
+  for (const [key, entries] of groups) {
+    const listed = entries.slice(0, REMOVAL_EXAMPLES).map((entry) => `\n  ${entry.file}${entry.symbol ? `#${entry.symbol} (${entry.kind})` : ""}`);
+    const more = entries.length > REMOVAL_EXAMPLES ? `\n  ... and ${entries.length - REMOVAL_EXAMPLES} more (use --json for the full list)` : "";
+    console.log(`${key} (${entries.length})${listed.join("")}${more}`);
+  }
+}
+
+function printEstimate(root: string, estimate: GraphEstimate): void {
+  const symbols = Object.entries(estimate.symbolsByKind)
+    .map(([kind, count]) => `${kind} ${count}`)
+    .join(", ");
+
+  console.log(`Dry run for ${path.resolve(root)}
+  Files: ${estimate.files} (skipped ${estimate.skipped["ignored-directory"]} ignored directories, ${estimate.skipped.ycgignore} .ycgignore matches, ${estimate.skipped.extension} files with other extensions)
+  Symbols (estimated): ${symbols || "none"}
+  Imports (estimated): ${estimate.imports}
+  Output tokens (estimated from ${estimate.sampledFiles} sampled files): ~${estimate.outputTokens}`);
+}
+
+async function confirm(question: string): Promise<boolean> {
+  if (!process.stdin.isTTY) {
+    return false;
+  }
+
+  const prompt = readline.createInterface({ input: process.stdin, output: process.stdout });
+  const answer = await prompt.question(`${question} [y/N] `);
+  prompt.close();
+  return /^y(es)?$/i.test(answer.trim());
+}
+
+function writeNewFile(filePath: string, content: string, force?: boolean): void {
+  if (fs.existsSync(filePath) && !force) {
+    fail(`${filePath} already exists; pass --force to overwrite it`);
+  }
+
+  ensureDirectoryExists(filePath);
+  fs.writeFileSync(filePath, content, "utf8");
+  console.log(`Wrote ${filePath}`);
+}
+
+async function runInit(argv: string[]): Promise<void> {
+  const options = parseInitArgs(argv);
+  const root = path.resolve(options.root);
+  const profile = detectProject(root);
+  const config = proposeConfig(profile);
+  const configPath = path.join(root, DEFAULT_CONFIG_FILE);
+
+  console.log(`Detected ${profile.fileCount} source files`);
+  console.log(`  Languages: ${profile.languages.join(", ") || "none"}`);
+  console.log(`  Frameworks: ${profile.frameworks.join(", ") || "none"}`);
+  if (profile.languages.some((language) => language !== "typescript" && language !== "javascript")) {
+    console.warn("Warning: ycg only analyzes JavaScript and TypeScript files; other languages are ignored");
+  }
+  console.log(`\nProposed ${DEFAULT_CONFIG_FILE}:\n${renderConfigFile(config)}`);
+
+  if (!options.yes && !(await confirm(`Write ${configPath}?`))) {
+    console.log("Aborted; pass --yes to write without confirmation");
+    return;
+  }
+
+  writeNewFile(configPath, renderConfigFile(config), options.force);
+  if (options.ci) {
+    writeNewFile(path.join(root, ciSnippetPath(options.ci)), renderCiSnippet(options.ci), options.force);
+  }
+}
+
+function runDoctorCommand(argv: string[]): void {
+  const checks = runDoctor(".");
+
+  if (argv.includes("--json")) {
+    console.log(JSON.stringify(checks, null, 2));
+  } else {
+    const labels: Record<DoctorCheck["status"], string> = { pass: "PASS", warn: "WARN", fail: "FAIL" };
+    for (const check of checks) {
+      console.log(`${labels[check.status]}  ${check.name}: ${check.detail}`);
+    }
+  }
+
+  if (checks.some((check) => check.status === "fail")) {
+    process.exitCode = 1;
+  }
+}
+
+function sourceTokens(graph: CodeGraph): number {
+  return graph.files.reduce((total, file) => {
+    const filePath = path.join(graph.root, file.path);
+    return total + (fs.existsSync(filePath) ? estimateTokens(fs.readFileSync(filePath, "utf8")) : 0);
+  }, 0);
+}
+
+function printStats(stats: GraphStats): void {
+  const kinds = Object.entries(stats.symbolsByKind)
+    .map(([kind, count]) => `${kind} ${count}`)
+    .join(", ");
+  const mostImported = stats.mostImported.map((entry) => `\n    ${entry.path} (${entry.importers})`).join("");
+  const ratio = stats.compressionRatio !== undefined ? ` (${(stats.compressionRatio * 100).toFixed(1)}% of ~${stats.sourceTokens} source tokens)` : "";
+
+  console.log(`Files: ${stats.files}
+Symbols: ${stats.symbols}${kinds ? ` (${kinds})` : ""}
+Imports: ${stats.imports.internal} internal, ${stats.imports.external} external
+Most imported files:${mostImported || " none"}
+Output tokens (estimated):
+  yaml: ~${stats.outputTokens.yaml}${ratio}
+  --short-keys: ~${stats.outputTokens.shortKeys}
+  --short-keys --no-metadata: ~${stats.outputTokens.shortKeysNoMetadata}`);
+}
+
+function printTokenReport(report: TokenReport): void {
+  const share = (tokens: number) => `~${tokens}${report.total > 0 ? ` (${((tokens / report.total) * 100).toFixed(1)}%)` : ""}`;
+  const kinds = Object.entries(report.bySymbolKind)
+    .sort(([, a], [, b]) => (b ?? 0) - (a ?? 0))
+    .map(([kind, tokens]) => `\n  ${kind}: ${share(tokens ?? 0)}`)
+    .join("");
+  const files = report.topFiles.map((entry) => `\n  ${String(entry.tokens).padStart(7)}  ${entry.path}`).join("");
+  const symbols = report.topSymbols.map((entry) => `\n  ${String(entry.tokens).padStart(7)}  ${entry.id} (${entry.kind})`).join("");
+
+  console.log(`Output tokens (estimated): ~${report.total}
+By content:
+  docs: ${share(report.byCategory.docs)}
+  signatures: ${share(report.byCategory.signatures)}
+  imports: ${share(report.byCategory.imports)}
+  structure: ${share(report.byCategory.structure)}
+  envelope: ${share(report.envelope)}
+By symbol kind:${kinds || " none"}
+Largest files:${files || " none"}
+Largest symbols:${symbols || " none"}`);
+}
+
+function runStats(argv: string[]): void {
+  const args = resolveOptions(parseArgs(argv));
+  const config = loadConfig(args);
+  const graph = buildGraph(args.root, { ...config, onDiagnostic: createReporter(args) });
+  if (argv.includes("--token-report")) {
+    const report = computeTokenReport(graph, config);
+    if (argv.includes("--json")) {
+      console.log(JSON.stringify(report, null, 2));
+    } else {
+      printTokenReport(report);
+    }
+    return;
+  }
+
+  const stats = computeGraphStats(graph, sourceTokens(graph));
+
+  if (argv.includes("--json")) {
+    console.log(JSON.stringify(stats, null, 2));
+  } else {
+    printStats(stats);
+  }
+}
+
+function fitWithinBudget(graph: CodeGraph, target: OutputTarget, options: CliOptions): { graph: CodeGraph; target: OutputTarget } {
+  if (options.maxTokens === undefined || target.format !== "yaml") {
+    return { graph, target };
+  }
+
+  const fitted = fitToTokenBudget(graph, options.maxTokens, target.options);
+  if (fitted.steps.length > 0) {
+    progress(options, `${target.path}: reduced to ~${fitted.tokens} tokens by ${fitted.steps.join(", ")}`);
+  }
+
+  return { graph: fitted.graph, target: { ...target, options: fitted.options } };
+}
+
+/** Warns that a graph comes from a newer release, so fields and kinds this one does not know pass through unchecked. */
+function warnNewerSchema(graph: CodeGraph, filePath: string): CodeGraph {
+  if (isNewerSchema(graph)) {
+    console.error(
+      formatDiagnostic({
+        level: "warning",
+        code: DiagnosticCodes.newerGraphSchema,
+        file: filePath,
+        message: `was written with graph schema ${graph.schemaVersion}, newer than ${GRAPH_SCHEMA_VERSION}; unknown fields are kept as they are`,
+        hint: "update ycg to read every field",
+      })
+    );
+  }
+
+  return graph;
+}
+
+function readGraphFile(filePath: string, warn = true): CodeGraph {
+  return readGraphDocumentFile(filePath, warn).graph;
+}
+
+function readGraphDocumentFile(filePath: string, warn = true): GraphDocument {
+  if (!fs.existsSync(filePath)) {
+    fail(`Graph file not found: ${filePath}`, "input", { path: filePath });
+  }
+
+  const document = readGraphDocument(fs.readFileSync(filePath, "utf8"));
+  if (warn) {
+    warnNewerSchema(document.graph, filePath);
+  }
+  return document;
+}
+
+function formatProblem(problem: GraphProblem): string {
+  return `${problem.level}: ${problem.file ? `${problem.file}: ` : ""}${problem.message}`;
+}
+
+function runValidate(argv: string[]): void {
+  const files = argv.filter((arg) => !arg.startsWith("-"));
+  if (files.length !== 1) {
+    fail("Usage: ycg validate <graph.yaml> [--json]");
+  }
+
+  let problems: GraphProblem[];
+  try {
+    problems = validateGraph(readGraphFile(files[0], false));
+  } catch (error) {
+    problems = [{ level: "error", message: (error as Error).message }];
+  }
+
+  if (argv.includes("--json")) {
+    console.log(JSON.stringify(problems, null, 2));
+  } else {
+    problems.forEach((problem) => console.log(formatProblem(problem)));
+    const errors = problems.filter((problem) => problem.level === "error").length;
+    console.log(`${files[0]}: ${errors} error(s), ${problems.length - errors} warning(s)`);
+  }
+
+  if (problems.some((problem) => problem.level === "error")) {
+    process.exitCode = 1;
+  }
+}
+
+function runDiff(argv: string[]): void {
+  const files: string[] = [];
+  let format: "text" | "yaml" | "json" = "text";
+  let renameThreshold = DEFAULT_RENAME_THRESHOLD;
+  let exitCode = false;
+
+  for (let i = 0; i < argv.length; i += 1) {
+    const arg = argv[i];
+    switch (arg) {
+      case "-f":
+      case "--format":
+        format = parseChoice("--format", argv[i + 1], ["text", "yaml", "json"] as const);
+        i += 1;
+        break;
+      case "--rename-threshold":
+        renameThreshold = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--exit-code":
+        exitCode = true;
+        break;
+      default:
+        files.push(arg);
+        break;
+    }
+  }
+
+  if (files.length !== 2) {
+    fail("Usage: ycg diff <before.yaml> <after.yaml> [--format text|yaml|json] [--rename-threshold <r>] [--exit-code]");
+  }
+  if (Number.isNaN(renameThreshold) || renameThreshold < 0) {
+    fail(`--rename-threshold must be a non-negative number, got ${renameThreshold}`, "config", { hint: "Use a value from 0 to 1; values above 1 turn rename detection off" });
+  }
+
+  const diff = diffGraphs(readGraphFile(files[0]), readGraphFile(files[1]), { renameThreshold });
+  process.stdout.write(renderGraphDiff(diff, format));
+
+  const changed = diff.addedFiles.length + diff.removedFiles.length + diff.symbols.length + diff.imports.length > 0;
+  if (exitCode && changed) {
+    process.exitCode = 1;
+  }
+}
+
+const QUERY_USAGE = "Usage: ycg query <graph.yaml> --symbol <selector> [--depth <n>] [--direction out|in|both] [--format yaml|html|dot|graphml] [--out <file>]";
+
+function runQuery(argv: string[]): void {
+  const files: string[] = [];
+  const selectors: string[] = [];
+  let depth = 1;
+  let direction: QueryDirection = "both";
+  let format: OutputFormat = "yaml";
+  let out = "-";
+
+  for (let i = 0; i < argv.length; i += 1) {
+    const arg = argv[i];
+    switch (arg) {
+      case "-i":
+      case "--input":
+        files.push(argv[i + 1]);
+        i += 1;
+        break;
+      case "-s":
+      case "--symbol":
+        selectors.push(argv[i + 1]);
+        i += 1;
+        break;
+      case "--depth":
+        depth = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "--direction":
+        direction = parseChoice("--direction", argv[i + 1], ["out", "in", "both"] as const);
+        i += 1;
+        break;
+      case "-f":
+      case "--format":
+        format = parseChoice("--format", argv[i + 1], OUTPUT_FORMATS);
+        i += 1;
+        break;
+      case "-o":
+      case "--out":
+        out = argv[i + 1];
+        i += 1;
+        break;
+      default:
+        files.push(arg);
+        break;
+    }
+  }
+
+  if (files.length !== 1 || selectors.length === 0 || selectors.some((selector) => !selector)) {
+    fail(QUERY_USAGE);
+  }
+  if (!Number.isInteger(depth) || depth < 0) {
+    fail(`--depth must be a non-negative integer, got ${depth}`);
+  }
+
+  const { graph, shortKeys, metadata } = readGraphDocumentFile(files[0]);
+  if (matchSymbols(graph, selectors).length === 0) {
+    fail(`No symbol in ${files[0]} matches ${selectors.join(", ")}`, "input", { path: files[0] });
+  }
+
+  // Answer in the key form of the input, so the subgraph can be fed wherever the full graph was.
+  const options = { shortKeys, metadata };
+  const subgraph = querySubgraph(graph, selectors, { depth, direction });
+  const errors = validateGraph(subgraph).filter((problem) => problem.level === "error");
+  if (errors.length > 0) {
+    fail(`Extracted subgraph failed validation:\n${errors.map(formatProblem).join("\n")}`, "validation");
+  }
+
+  const target = { path: out, format, options };
+  if (out === "-") {
+    streamOutput(subgraph, target, (chunk) => process.stdout.write(chunk));
+    return;
+  }
+
+  const outputPath = path.resolve(out);
+  ensureDirectoryExists(outputPath);
+  writeFileAtomic(outputPath, (write) => streamOutput(subgraph, target, write));
+  console.error(`Subgraph with ${subgraph.files.length} files written to ${outputPath}`);
+}
+
+const MIGRATE_USAGE = "Usage: ycg migrate <graph.yaml> [--to-version <n>] [--out <file>]";
+
+function runMigrate(argv: string[]): void {
+  const files: string[] = [];
+  let toVersion = GRAPH_SCHEMA_VERSION;
+  let out = "-";
+
+  for (let i = 0; i < argv.length; i += 1) {
+    const arg = argv[i];
+    switch (arg) {
+      case "-i":
+      case "--input":
+        files.push(argv[i + 1]);
+        i += 1;
+        break;
+      case "--to-version":
+        toVersion = Number(argv[i + 1]);
+        i += 1;
+        break;
+      case "-o":
+      case "--out":
+        out = argv[i + 1];
+        i += 1;
+        break;
+      default:
+        files.push(arg);
+        break;
+    }
+  }
+
+  if (files.length !== 1 || !files[0]) {
+    fail(MIGRATE_USAGE);
+  }
+  if (toVersion !== GRAPH_SCHEMA_VERSION) {
+    fail(`Cannot migrate to graph schema ${toVersion}; this version writes schema ${GRAPH_SCHEMA_VERSION} only`, "config", { hint: `Try --to-version ${GRAPH_SCHEMA_VERSION}` });
+  }
+
+  const { graph, shortKeys, metadata, schemaVersion: from } = readGraphDocumentFile(files[0], false);
+  if (isNewerSchema(graph)) {
+    fail(`${files[0]} uses graph schema ${graph.schemaVersion}, newer than ${GRAPH_SCHEMA_VERSION}; it cannot be migrated back`, "input", {
+      path: files[0],
+      hint: "update ycg to read it",
+    });
+  }
+
+  // Keep the key form of the input, like `ycg query` does.
+  const target: OutputTarget = { path: out, format: "yaml", options: { shortKeys, metadata } };
+  if (out === "-") {
+    streamOutput(graph, target, (chunk) => process.stdout.write(chunk));
+  } else {
+    const outputPath = path.resolve(out);
+    ensureDirectoryExists(outputPath);
+    writeFileAtomic(outputPath, (write) => streamOutput(graph, target, write));
+  }
+  console.error(`Migrated ${files[0]} from graph schema ${from} to ${GRAPH_SCHEMA_VERSION}${out === "-" ? "" : ` (${path.resolve(out)})`}`);
+}
+
+function writeChunks(graph: CodeGraph, target: OutputTarget, options: CliOptions): number {
+  const outputPath = path.resolve(target.path);
+  const chunks = chunkGraph(graph, options.chunkTokens ?? Infinity, target.options);
+  ensureDirectoryExists(outputPath);
+
+  chunks.forEach((chunk, index) => {
+    writeFileAtomic(chunkPath(outputPath, index), (write) => streamOutput(chunk.graph, target, write));
+    if (options.chunkTokens !== undefined && chunk.tokens > options.chunkTokens) {
+      progress(options, `${chunkPath(outputPath, index)}: a single file needs ~${chunk.tokens} tokens, over --chunk-tokens`);
+    }
+  });
+  writeFileAtomic(manifestPath(outputPath), renderChunkManifest(chunks, outputPath));
+  progress(options, `Graph written to ${chunks.length} part(s) listed in ${manifestPath(outputPath)}`);
+  return chunks.reduce((total, chunk) => total + chunk.tokens, 0);
+}
+
+interface GenerationSummary {
+  files: number;
+  symbols: number;
+  imports: number;
+  tokens: number;
+  warnings: number;
+}
+
+/** Builds the graph and writes every output target; throws instead of exiting so --watch can retry. */
+function generate(args: CliOptions, config: ValidatedConfig, targets: OutputTarget[]): GenerationSummary {
+  let warnings = 0;
+  const report = createReporter(args, (diagnostic) => {
+    warnings += diagnostic.level === "warning" ? 1 : 0;
+  });
+  const cache = args.cacheDir ? openParseCache(args.cacheDir, config) : undefined;
+  const merges: DeclarationMerge[] = [];
+  const built = buildGraph(args.root, { ...config, cache, onDiagnostic: report, onDeclarationMerge: (merge) => merges.push(merge) });
+  cache?.save();
+  const graph = args.embedMetadata ? embedRunMetadata(built, config) : built;
+  reportParseHealth(graph, args, report);
+  reportUnresolvedImports(graph, args, report);
+
+  const errors = args.validate === false ? [] : validateGraph(graph).filter((problem) => problem.level === "error");
+  if (errors.length > 0) {
+    throw new CliError("validation", `Generated graph failed validation:\n${errors.map(formatProblem).join("\n")}\nNo output was written.`);
+  }
+
+  const visualization = {
+    styles: args.styles,
+    onWarning: (message: string) => report({ level: "warning", code: DiagnosticCodes.unknownStyle, message }),
+  };
+
+  const symbols = graph.files.reduce((total, file) => total + file.symbols.length, 0);
+  const imports = graph.files.reduce((total, file) => total + file.imports.length, 0);
+  if (args.verbose) {
+    progress(args, `Graph has ${graph.files.length} files, ${symbols} symbols, ${imports} imports`);
+    if (cache) {
+      progress(args, `Parse cache: ${cache.hits} reused, ${cache.misses} parsed`);
+    }
+    if (merges.length > 0) {
+      const merged = merges.reduce((total, merge) => total + merge.merged, 0);
+      progress(args, `Declaration files: merged ${merged} symbols from ${merges.length} .d.ts files into their implementations`);
+    }
+  }
+
+  let totalTokens = 0;
+  for (const target of targets) {
+    const output = fitWithinBudget(applyOutputFilters(graph, target.filters), target, args);
+    if (args.chunkTokens !== undefined && target.format === "yaml") {
+      totalTokens += writeChunks(output.graph, output.target, args);
+      continue;
+    }
+
+    let tokens = 0;
+    const emit = (write: (chunk: string) => void) =>
+      streamOutput(
+        output.graph,
+        output.target,
+        (chunk) => {
+          tokens += estimateTokens(chunk);
+          write(chunk);
+        },
+        visualization
+      );
+
+    if (target.path === "-") {
+      emit((chunk) => process.stdout.write(chunk));
+      totalTokens += tokens;
+      continue;
+    }
+
+    const outputPath = path.resolve(target.path);
+    ensureDirectoryExists(outputPath);
+    writeFileAtomic(outputPath, emit);
+    totalTokens += tokens;
+    progress(args, `Graph written to ${outputPath}${args.verbose ? ` (~${tokens} tokens)` : ""}`);
+  }
+
+  return { files: graph.files.length, symbols, imports, tokens: totalTokens, warnings };
+}
+
+function formatChange(current: number, previous: number | undefined): string {
+  if (previous === undefined || current === previous) {
+    return `${current}`;
+  }
+  return `${current} (${current > previous ? "+" : ""}${current - previous})`;
+}
+
+function formatSummary(summary: GenerationSummary, previous?: GenerationSummary): string {
+  return [
+    `${formatChange(summary.files, previous?.files)} files`,
+    `${formatChange(summary.symbols, previous?.symbols)} symbols`,
+    `${formatChange(summary.imports, previous?.imports)} imports`,
+    `~${formatChange(summary.tokens, previous?.tokens)} tokens`,
+  ].join(", ");
+}
+
+function watchAndGenerate(args: CliOptions, config: ValidatedConfig, targets: OutputTarget[]): void {
+  let previous: GenerationSummary | undefined;
+  const run = () => {
+    const summary = generate(args, config, targets);
+    console.error(`[${new Date().toLocaleTimeString()}] ${formatSummary(summary, previous)}`);
+    previous = summary;
+  };
+
+  const loop = createWatchLoop(run, {
+    debounceMs: args.watchDebounce,
+    onError: (error) => console.error(`[${new Date().toLocaleTimeString()}] ${describeError(error)}\nWaiting for the next change to retry.`),
+  });
+  const exclude = [...targets.map((target) => path.resolve(target.path)), ...(args.cacheDir ? [path.resolve(args.cacheDir)] : [])];
+  const stop = watchSourceTree(path.resolve(args.root), () => loop.notify(), { extensions: config.extensions, exclude });
+
+  loop.notify();
+  loop.flush();
+  console.error(`Watching ${path.resolve(args.root)} for changes (Ctrl+C to stop)`);
+
+  process.once("SIGINT", () => {
+    stop();
+    loop.close();
+  });
+}
+
+function runGenerate(argv: string[]): void {
+  const args = resolveOptions(parseArgs(argv));
 
   if (args.help) {
     printHelp();
     process.exit(0);
   }
 
-  const graph = buildGraph(args.root, { extensions: args.extensions });
-  const serialized = serializeGraph(graph);
-  const outputPath = path.resolve(args.out);
+  const config = loadConfig(args);
 
-  ensureDirectoryExists(outputPath);
-  fs.writeFileSync(outputPath, serialized, "utf8");
-  console.log(`Graph written to ${outputPath}`);
+  if (args.dryRun) {
+    printEstimate(args.root, estimateGraph(args.root, config));
+    return;
+  }
+
+  if (args.explainFiltering) {
+    const removals: Removal[] = [];
+    buildGraph(args.root, { ...config, onDiagnostic: createReporter(args), onRemoval: (removal) => removals.push(removal) });
+    if (args.json) {
+      console.log(JSON.stringify(removals, null, 2));
+    } else {
+      printRemovals(removals);
+    }
+    return;
+  }
+
+  const format = args.format ?? "yaml";
+  const targets: OutputTarget[] = withConfigErrors(() =>
+    args.emit?.length
+      ? args.emit.map((spec) => parseEmitSpec(spec, config, format))
+      : [checkOutputTarget({ path: args.out, format, options: config })]
+  );
+
+  if (args.maxTokens !== undefined && (!Number.isInteger(args.maxTokens) || args.maxTokens <= 0)) {
+    fail(`--max-tokens must be a positive integer, got ${args.maxTokens}`);
+  }
+  if (args.chunkTokens !== undefined && (!Number.isInteger(args.chunkTokens) || args.chunkTokens <= 0)) {
+    fail(`--chunk-tokens must be a positive integer, got ${args.chunkTokens}`);
+  }
+  if (args.chunkTokens !== undefined && targets.some((target) => target.path === "-")) {
+    fail("--chunk-tokens writes several files and cannot be combined with --out -");
+  }
+  if (args.watch && targets.some((target) => target.path === "-")) {
+    fail("--watch rewrites the output files on every change and cannot be combined with --out -");
+  }
+  if (args.watchDebounce !== undefined && (!Number.isInteger(args.watchDebounce) || args.watchDebounce < 0)) {
+    fail(`--watch-debounce must be a non-negative integer, got ${args.watchDebounce}`);
+  }
+
+  if (args.watch) {
+    watchAndGenerate(args, config, targets);
+    return;
+  }
+
+  const summary = generate(args, config, targets);
+  if (args.failOnWarnings && summary.warnings > 0) {
+    console.error(`${summary.warnings} warning(s) reported; failing because of --fail-on-warnings`);
+    process.exitCode = 1;
+  }
+}
+
+function describeError(error: Error): string {
+  if (error instanceof TokenBudgetError) {
+    return `${error.message}\nNo output was written; raise --max-tokens or narrow the graph with --focus-path.`;
+  }
+  if (error instanceof SerializationError) {
+    return `${error.message}\nNo output was written.`;
+  }
+  return error.message;
+}
+
+function errorCategory(error: Error): ErrorCategory {
+  if (error instanceof CliError) {
+    return error.category;
+  }
+  if (error instanceof ConfigError) {
+    return "config";
+  }
+  if (error instanceof TokenBudgetError) {
+    return "validation";
+  }
+  if (error instanceof SerializationError) {
+    return "output";
+  }
+
+  // Node file system errors: failing to write or rename an output versus failing to read the project.
+  const syscall = (error as NodeJS.ErrnoException).syscall;
+  if (syscall) {
+    return ["write", "rename", "mkdir", "unlink"].includes(syscall) ? "output" : "input";
+  }
+  return "internal";
+}
+
+/** Removes `--error-format <f>`, which applies to every command, and returns the remaining arguments. */
+function readErrorFormat(argv: string[]): string[] {
+  const index = argv.indexOf("--error-format");
+  if (index === -1) {
+    return argv;
+  }
+
+  errorFormat = parseChoice("--error-format", argv[index + 1], ["human", "json"] as const);
+  return [...argv.slice(0, index), ...argv.slice(index + 2)];
+}
+
+async function main(): Promise<void> {
+  const argv = readErrorFormat(process.argv.slice(2));
+  const [command, ...rest] = argv;
+
+  switch (command) {
+    case "init":
+      await runInit(rest);
+      break;
+    case "doctor":
+      runDoctorCommand(rest);
+      break;
+    case "stats":
+      runStats(rest);
+      break;
+    case "validate":
+      runValidate(rest);
+      break;
+    case "diff":
+      runDiff(rest);
+      break;
+    case "query":
+      runQuery(rest);
+      break;
+    case "migrate":
+      runMigrate(rest);
+      break;
+    default:
+      runGenerate(argv);
+      break;
+  }
 }
 
-run();
+main().catch((error) => {
+  const cause = error as Error;
+  const details = cause instanceof CliError ? { path: cause.path, hint: cause.hint } : cause instanceof ConfigError ? { hint: cause.suggestion } : {};
+  fail(describeError(cause), errorCategory(cause), details);
+});
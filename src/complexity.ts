@@ -0,0 +1,66 @@
+import ts from "typescript";
+
+export interface ComplexityMetrics {
+  /** Number of branching constructs: if/for/while/case/catch/conditional expressions and `&&`/`||`/`??`. */
+  complexity: number;
+  /** Lines spanned by the declaration. */
+  loc: number;
+}
+
+function isBranch(node: ts.Node): boolean {
+  switch (node.kind) {
+    case ts.SyntaxKind.IfStatement:
+    case ts.SyntaxKind.ForStatement:
+    case ts.SyntaxKind.ForInStatement:
+    case ts.SyntaxKind.ForOfStatement:
+    case ts.SyntaxKind.WhileStatement:
+    case ts.SyntaxKind.DoStatement:
+    case ts.SyntaxKind.CaseClause:
+    case ts.SyntaxKind.CatchClause:
+    case ts.SyntaxKind.ConditionalExpression:
+      return true;
+    case ts.SyntaxKind.BinaryExpression: {
+      const operator = (node as ts.BinaryExpression).operatorToken.kind;
+      return (
+        operator === ts.SyntaxKind.AmpersandAmpersandToken ||
+        operator === ts.SyntaxKind.BarBarToken ||
+        operator === ts.SyntaxKind.QuestionQuestionToken
+      );
+    }
+    default:
+      return false;
+  }
+}
+
+function countBranches(node: ts.Node): number {
+  let count = 0;
+  node.forEachChild((child) => {
+    count += (isBranch(child) ? 1 : 0) + countBranches(child);
+  });
+  return count;
+}
+
+export function measureComplexity(node: ts.Node, sourceFile: ts.SourceFile): ComplexityMetrics {
+  const start = sourceFile.getLineAndCharacterOfPosition(node.getStart(sourceFile)).line;
+  const end = sourceFile.getLineAndCharacterOfPosition(node.getEnd()).line;
+  return { complexity: countBranches(node), loc: end - start + 1 };
+}
+
+/**
+ * Returns the function-like node a declaration stands for: the declaration itself for functions,
+ * or the initializer of a variable bound to an arrow function or function expression.
+ */
+export function functionLikeOf(node: ts.Node): ts.FunctionLikeDeclaration | undefined {
+  if (ts.isFunctionDeclaration(node)) {
+    return node;
+  }
+
+  if (ts.isVariableDeclaration(node) && node.initializer) {
+    const initializer = node.initializer;
+    if (ts.isArrowFunction(initializer) || ts.isFunctionExpression(initializer)) {
+      return initializer;
+    }
+  }
+
+  return undefined;
+}
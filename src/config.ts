@@ -0,0 +1,260 @@
+import fs from "fs";
+import path from "path";
+import { DECLARATION_MODES } from "./declarations";
+import { IMPORT_EDGE_KINDS } from "./edges";
+import { GraphOptions } from "./graph";
+import { OUTPUT_FORMATS, OutputFormat } from "./outputs";
+import { NODE_ROLES } from "./roles";
+import { StyleConfig } from "./styles";
+import { CLASS_SUMMARY_MODES } from "./summaries";
+import { TEST_FILE_MODES } from "./testFiles";
+import { SYMBOL_KINDS } from "./validate";
+import { GRAPH_SECTIONS, SerializeOptions } from "./yamlSerializer";
+
+export interface YcgConfig extends GraphOptions, SerializeOptions {
+  minParseHealth?: number;
+  /** Format of the main output (default: yaml). */
+  format?: OutputFormat;
+}
+
+/** Contents of a `ycg.config.json` file: any configuration value plus the scan root and output path. */
+export interface ConfigFile extends Omit<YcgConfig, "onDiagnostic" | "onDeclarationMerge" | "onRemoval" | "cache"> {
+  root?: string;
+  out?: string;
+  /** Directory holding the parse cache between runs. */
+  cacheDir?: string;
+  /** Colors, shapes, line styles and visibility per node kind and edge type for visual outputs. */
+  styles?: StyleConfig;
+}
+
+export const DEFAULT_CONFIG_FILE = "ycg.config.json";
+
+type ConfigValueType = "string" | "boolean" | "number" | "string list" | "object";
+
+/** Every key a configuration file may hold, and the JSON type of its value. */
+const CONFIG_FILE_KEYS: Record<keyof ConfigFile, ConfigValueType> = {
+  root: "string",
+  out: "string",
+  cacheDir: "string",
+  styles: "object",
+  format: "string",
+  minParseHealth: "number",
+  shortKeys: "boolean",
+  metadata: "boolean",
+  sections: "string list",
+  extensions: "string list",
+  tests: "string",
+  declarationFiles: "string",
+  declarationRewrites: "object",
+  tsconfig: "string",
+  focusPaths: "string list",
+  focusDepth: "number",
+  keepRoles: "string list",
+  includeKinds: "string list",
+  excludeKinds: "string list",
+  publicOnly: "boolean",
+  excludeImports: "string list",
+  dropSelfImports: "boolean",
+  pruneOrphans: "boolean",
+  externalNoiseThreshold: "number",
+  reverseImports: "boolean",
+  fileDependencies: "boolean",
+  collapseBarrels: "boolean",
+  packages: "boolean",
+  classSummaries: "string",
+  complexity: "boolean",
+  ranges: "boolean",
+  docPolicy: "object",
+  docs: "boolean",
+  signatures: "boolean",
+  implementations: "boolean",
+  members: "boolean",
+};
+
+/** Keys holding file system paths, which are relative to the configuration file rather than the working directory. */
+const CONFIG_FILE_PATHS: (keyof ConfigFile)[] = ["root", "out", "cacheDir", "tsconfig"];
+
+/**
+ * A configuration whose cross-field invariants have been checked and whose values are normalized.
+ * Only `validateConfig` produces one.
+ */
+export type ValidatedConfig = Readonly<YcgConfig> & { readonly __validated: true };
+
+export type ConfigErrorKind = "invalid-value" | "conflicting-flags";
+
+export class ConfigError extends Error {
+  readonly kind: ConfigErrorKind;
+  readonly suggestion?: string;
+
+  constructor(kind: ConfigErrorKind, message: string, suggestion?: string) {
+    super(message);
+    this.name = "ConfigError";
+    this.kind = kind;
+    this.suggestion = suggestion;
+  }
+}
+
+function normalizeExtensions(extensions?: string[]): string[] | undefined {
+  if (extensions === undefined) {
+    return undefined;
+  }
+
+  const normalized = Array.from(
+    new Set(extensions.map((ext) => ext.trim()).filter(Boolean).map((ext) => (ext.startsWith(".") ? ext : `.${ext}`)))
+  );
+  if (normalized.length === 0) {
+    throw new ConfigError("invalid-value", "No file extensions given", "Pass a comma-separated list such as --extensions .ts,.tsx");
+  }
+
+  return normalized;
+}
+
+function checkRatio(name: string, value?: number): void {
+  if (value !== undefined && (!Number.isFinite(value) || value < 0 || value > 1)) {
+    throw new ConfigError("invalid-value", `${name} must be a number between 0 and 1, got ${value}`, `Try ${name} 0.9`);
+  }
+}
+
+function checkChoice(name: string, value: unknown, choices: readonly string[]): void {
+  if (value !== undefined && !choices.includes(value as string)) {
+    throw new ConfigError(
+      "invalid-value",
+      `${name} must be one of ${choices.join(", ")}, got ${JSON.stringify(value)}`,
+      `Supported values: ${choices.join(", ")}`
+    );
+  }
+}
+
+function checkChoices(name: string, values: unknown, choices: readonly string[]): void {
+  if (values === undefined) {
+    return;
+  }
+  if (!Array.isArray(values)) {
+    throw new ConfigError(
+      "invalid-value",
+      `${name} must be a list, got ${JSON.stringify(values)}`,
+      `Supported values: ${choices.join(", ")}`
+    );
+  }
+  values.forEach((value) => checkChoice(name, value, choices));
+}
+
+/**
+ * Checks every allowed value and cross-field invariant of a configuration up front and returns a
+ * normalized copy, throwing a ConfigError with an actionable suggestion on the first violation.
+ */
+export function validateConfig(config: YcgConfig): ValidatedConfig {
+  // Config files are plain JSON, so values the CLI would have rejected can arrive here unchecked.
+  checkChoice("--format", config.format, OUTPUT_FORMATS);
+  checkChoice("--class-summaries", config.classSummaries, CLASS_SUMMARY_MODES);
+  checkChoice("--tests", config.tests, TEST_FILE_MODES);
+  checkChoice("declarationFiles", config.declarationFiles, DECLARATION_MODES);
+  checkChoices("--sections", config.sections, GRAPH_SECTIONS);
+  checkChoices("--include-kinds", config.includeKinds, SYMBOL_KINDS);
+  checkChoices("--exclude-kinds", config.excludeKinds, SYMBOL_KINDS);
+  checkChoices("--exclude-imports", config.excludeImports, IMPORT_EDGE_KINDS);
+  checkChoices("--keep-role", config.keepRoles, NODE_ROLES);
+  checkRatio("--min-parse-health", config.minParseHealth);
+  if (config.focusDepth !== undefined && (!Number.isInteger(config.focusDepth) || config.focusDepth < 0)) {
+    throw new ConfigError("invalid-value", `--depth must be a non-negative integer, got ${config.focusDepth}`, "Try --depth 1");
+  }
+  if (config.externalNoiseThreshold !== undefined && (!Number.isInteger(config.externalNoiseThreshold) || config.externalNoiseThreshold < 1)) {
+    throw new ConfigError(
+      "invalid-value",
+      `--external-noise-threshold must be a positive integer, got ${config.externalNoiseThreshold}`,
+      "Try --external-noise-threshold 25"
+    );
+  }
+  const maxChars = config.docPolicy?.maxChars;
+  if (maxChars !== undefined && (!Number.isInteger(maxChars) || maxChars < 4)) {
+    throw new ConfigError("invalid-value", `--doc-max-chars must be an integer of at least 4, got ${maxChars}`, "Try --doc-max-chars 200");
+  }
+  if (config.docs === false && config.docPolicy) {
+    throw new ConfigError(
+      "conflicting-flags",
+      "--doc-max-chars, --doc-first-sentence and --doc-strip-tags have no effect with --no-docs",
+      "Drop either --no-docs or the doc options"
+    );
+  }
+  if (config.docs === false && config.classSummaries === "doc-only") {
+    throw new ConfigError(
+      "conflicting-flags",
+      "--class-summaries doc-only needs doc comments, which --no-docs skips",
+      "Use --class-summaries heuristic or drop --no-docs"
+    );
+  }
+  const overlapping = config.includeKinds?.filter((kind) => config.excludeKinds?.includes(kind)) ?? [];
+  if (overlapping.length > 0) {
+    throw new ConfigError(
+      "conflicting-flags",
+      `${overlapping.join(", ")} is both included and excluded`,
+      "List each kind in only one of --include-kinds and --exclude-kinds"
+    );
+  }
+  if (config.focusDepth !== undefined && !config.focusPaths?.length) {
+    throw new ConfigError("conflicting-flags", "--depth has no effect without --focus-path", "Add --focus-path src/billing/");
+  }
+
+  return Object.freeze({
+    ...config,
+    extensions: normalizeExtensions(config.extensions),
+    __validated: true as const,
+  });
+}
+
+function hasType(value: unknown, type: ConfigValueType): boolean {
+  switch (type) {
+    case "string list":
+      return Array.isArray(value) && value.every((entry) => typeof entry === "string");
+    case "object":
+      return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+    default:
+      return typeof value === type;
+  }
+}
+
+function checkConfigFileKeys(filePath: string, config: Record<string, unknown>): void {
+  const known = Object.keys(CONFIG_FILE_KEYS);
+  for (const [key, value] of Object.entries(config)) {
+    const type = CONFIG_FILE_KEYS[key as keyof ConfigFile];
+    if (!type) {
+      const similar = known.find((candidate) => candidate.toLowerCase() === key.toLowerCase());
+      throw new ConfigError(
+        "invalid-value",
+        `Unknown key "${key}" in ${filePath}`,
+        similar ? `Did you mean "${similar}"?` : `Supported keys: ${known.join(", ")}`
+      );
+    }
+    if (!hasType(value, type)) {
+      const article = type === "object" ? "an" : "a";
+      throw new ConfigError("invalid-value", `"${key}" in ${filePath} must be ${article} ${type}, got ${JSON.stringify(value)}`);
+    }
+  }
+}
+
+/**
+ * Reads a configuration file, rejecting unknown keys and values of the wrong JSON type. Relative paths in
+ * `root`, `out`, `cacheDir`, and `tsconfig` are resolved against the directory of the file, so the
+ * configuration means the same from any working directory.
+ */
+export function loadConfigFile(filePath: string): ConfigFile {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(fs.readFileSync(filePath, "utf8"));
+  } catch (error) {
+    throw new ConfigError("invalid-value", `Cannot read ${filePath}: ${(error as Error).message}`, "Run `ycg init` to generate a valid file");
+  }
+
+  if (!parsed || typeof parsed !== "object" || Array.isArray(parsed)) {
+    throw new ConfigError("invalid-value", `${filePath} must contain a JSON object`);
+  }
+
+  const config = parsed as Record<string, unknown>;
+  checkConfigFileKeys(filePath, config);
+  const directory = path.dirname(path.resolve(filePath));
+  const paths = CONFIG_FILE_PATHS.filter((key) => typeof config[key] === "string" && config[key] !== "-").map((key) => [
+    key,
+    path.resolve(directory, config[key] as string),
+  ]);
+  return { ...config, ...Object.fromEntries(paths) } as ConfigFile;
+}
@@ -0,0 +1,93 @@
+import { FileGraph, SymbolNode } from "./types";
+
+export type DeclarationMode = "merge" | "keep";
+
+export const DECLARATION_MODES: readonly DeclarationMode[] = ["merge", "keep"];
+
+/** Directory names rewritten when looking for the implementation of a declaration file. */
+export const DEFAULT_DECLARATION_REWRITES: Record<string, string> = { dist: "src", lib: "src", types: "src" };
+
+const DECLARATION_SUFFIX = /\.d\.([cm]?ts)$/;
+
+export interface DeclarationMerge {
+  declaration: string;
+  implementation: string;
+  /** Number of declaration symbols dropped because the implementation defines them. */
+  merged: number;
+}
+
+export function isDeclarationFile(filePath: string): boolean {
+  return DECLARATION_SUFFIX.test(filePath);
+}
+
+function implementationCandidates(declarationPath: string, rewrites: Record<string, string>): string[] {
+  const segments = declarationPath.split("/");
+  const rewritten = segments.map((segment, index) => (index < segments.length - 1 ? rewrites[segment] ?? segment : segment)).join("/");
+  const bases = rewritten === declarationPath ? [declarationPath] : [declarationPath, rewritten];
+
+  return bases.flatMap((base) => {
+    const extension = base.match(DECLARATION_SUFFIX)?.[1] ?? "ts";
+    const stem = base.replace(DECLARATION_SUFFIX, "");
+    return extension === "ts" ? [`${stem}.ts`, `${stem}.tsx`] : [`${stem}.${extension}`];
+  });
+}
+
+function fillFromDeclaration(symbol: SymbolNode, declaration?: SymbolNode): SymbolNode {
+  if (!declaration) {
+    return symbol;
+  }
+
+  return {
+    ...symbol,
+    ...(symbol.doc === undefined && declaration.doc !== undefined ? { doc: declaration.doc } : {}),
+    ...(symbol.signature === undefined && declaration.signature !== undefined ? { signature: declaration.signature } : {}),
+  };
+}
+
+/**
+ * Drops symbols of `.d.ts` files that an implementation file in the same graph already defines.
+ * The implementation is looked up next to the declaration and under `rewrites` (e.g. `dist/` → `src/`).
+ * The implementation's symbols take the declaration's doc and signature when they have none, which is
+ * common for docs written only in hand-maintained declarations. Declaration files without an
+ * implementation, such as pure type packages, are kept as-is; declaration files left without symbols
+ * are removed. `onMerge` is called once per declaration file that had an implementation.
+ */
+export function mergeDeclarationFiles(
+  files: FileGraph[],
+  rewrites: Record<string, string> = DEFAULT_DECLARATION_REWRITES,
+  onMerge?: (merge: DeclarationMerge) => void
+): FileGraph[] {
+  const byPath = new Map(files.map((file) => [file.path, file]));
+  const declarationsOf = new Map<string, FileGraph[]>();
+  const remaining = new Map<string, SymbolNode[]>();
+
+  for (const file of files.filter((candidate) => isDeclarationFile(candidate.path))) {
+    const implementation = implementationCandidates(file.path, rewrites)
+      .map((candidate) => byPath.get(candidate))
+      .find((candidate): candidate is FileGraph => candidate !== undefined);
+    if (!implementation) {
+      continue;
+    }
+
+    const implemented = new Set(implementation.symbols.map((symbol) => symbol.name));
+    const symbols = file.symbols.filter((symbol) => !implemented.has(symbol.name));
+    remaining.set(file.path, symbols);
+    declarationsOf.set(implementation.path, [...(declarationsOf.get(implementation.path) ?? []), file]);
+    onMerge?.({ declaration: file.path, implementation: implementation.path, merged: file.symbols.length - symbols.length });
+  }
+
+  return files.flatMap((file) => {
+    const symbols = remaining.get(file.path);
+    if (symbols) {
+      return symbols.length > 0 ? [{ ...file, symbols }] : [];
+    }
+
+    const declarations = declarationsOf.get(file.path);
+    if (!declarations) {
+      return [file];
+    }
+
+    const declared = new Map(declarations.flatMap((declaration) => declaration.symbols).map((symbol) => [symbol.name, symbol]));
+    return [{ ...file, symbols: file.symbols.map((symbol) => fillFromDeclaration(symbol, declared.get(symbol.name))) }];
+  });
+}
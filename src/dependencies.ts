@@ -0,0 +1,23 @@
+import { CodeGraph } from "./types";
+
+/**
+ * Sets `dependsOn` on every file that imports other files of the graph, listing each imported file once
+ * in path order: a module-level view of the import structure without specifiers or imported names.
+ * Like `addReverseImports`, run it after all filtering.
+ */
+export function addFileDependencies(graph: CodeGraph): CodeGraph {
+  const paths = new Set(graph.files.map((file) => file.path));
+
+  return {
+    ...graph,
+    files: graph.files.map((file) => {
+      const targets = new Set<string>();
+      for (const edge of file.imports) {
+        if (edge.resolved && edge.resolved !== file.path && paths.has(edge.resolved)) {
+          targets.add(edge.resolved);
+        }
+      }
+      return targets.size > 0 ? { ...file, dependsOn: Array.from(targets).sort() } : file;
+    }),
+  };
+}
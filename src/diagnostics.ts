@@ -0,0 +1,32 @@
+export type DiagnosticLevel = "warning" | "error";
+
+/**
+ * Stable diagnostic codes. Codes are never reused, so suppression lists (`--allow YCG001`) and
+ * CI annotations keep working across releases.
+ */
+export const DiagnosticCodes = {
+  binaryFile: "YCG001",
+  invalidUtf8: "YCG002",
+  parseErrors: "YCG003",
+  unknownStyle: "YCG004",
+  unresolvedImport: "YCG005",
+  barrelImportDropped: "YCG006",
+  newerGraphSchema: "YCG007",
+} as const;
+
+export type DiagnosticCode = (typeof DiagnosticCodes)[keyof typeof DiagnosticCodes];
+
+export interface Diagnostic {
+  level: DiagnosticLevel;
+  code: DiagnosticCode;
+  message: string;
+  file?: string;
+  line?: number;
+  hint?: string;
+}
+
+export function formatDiagnostic(diagnostic: Diagnostic): string {
+  const location = diagnostic.file ? `${diagnostic.file}${diagnostic.line ? `:${diagnostic.line}` : ""}: ` : "";
+  const hint = diagnostic.hint ? ` (${diagnostic.hint})` : "";
+  return `${diagnostic.level}[${diagnostic.code}]: ${location}${diagnostic.message}${hint}`;
+}
@@ -0,0 +1,312 @@
+import YAML from "yaml";
+import { CodeGraph, FileGraph, SymbolNode } from "./types";
+
+export type SymbolChangeKind = "added" | "removed" | "moved" | "renamed" | "signature-changed" | "export-changed";
+
+/** Evidence weighed when pairing a removed symbol with an added one of another name. */
+export type SimilaritySignal = "signature" | "doc" | "members" | "imports" | "name";
+
+export interface SymbolChange {
+  change: SymbolChangeKind;
+  name: string;
+  kind: SymbolNode["kind"];
+  /** File of the symbol in the newer graph, or in the older one for removals. */
+  file: string;
+  /** Previous file of a moved symbol, or of a renamed one that also changed files. */
+  from?: string;
+  /** Previous name of a renamed symbol. */
+  previousName?: string;
+  before?: string;
+  after?: string;
+  /** Similarity, from 0 to 1, of a symbol matched by `renameThreshold` rather than by name. */
+  score?: number;
+  /** Similarity of each signal present on either version of a renamed symbol. */
+  evidence?: Partial<Record<SimilaritySignal, number>>;
+}
+
+export interface DiffOptions {
+  /**
+   * Minimum similarity for pairing a removed symbol with an added one of the same kind and another name,
+   * reported as renamed (default: 0.75). Values above 1 turn rename detection off.
+   */
+  renameThreshold?: number;
+}
+
+export const DEFAULT_RENAME_THRESHOLD = 0.75;
+
+export interface ImportChange {
+  file: string;
+  added: string[];
+  removed: string[];
+}
+
+export interface GraphDiff {
+  addedFiles: string[];
+  removedFiles: string[];
+  symbols: SymbolChange[];
+  imports: ImportChange[];
+}
+
+function symbolKey(symbol: SymbolNode): string {
+  return `${symbol.kind}:${symbol.name}`;
+}
+
+function importTargets(file: FileGraph): Set<string> {
+  return new Set(file.imports.map((edge) => edge.resolved ?? edge.from));
+}
+
+function compareSymbols(file: string, before: SymbolNode, after: SymbolNode): SymbolChange[] {
+  const base = { name: after.name, kind: after.kind, file };
+  return [
+    ...((before.signature ?? "") !== (after.signature ?? "")
+      ? [{ ...base, change: "signature-changed" as const, before: before.signature, after: after.signature }]
+      : []),
+    ...(before.exported !== after.exported
+      ? [{ ...base, change: "export-changed" as const, before: String(before.exported), after: String(after.exported) }]
+      : []),
+  ];
+}
+
+const SIGNAL_WEIGHTS: Record<SimilaritySignal, number> = { signature: 3, doc: 2, members: 2, imports: 1, name: 1 };
+
+/** 1 minus the edit distance of `a` and `b` divided by the length of the longer one. */
+function editSimilarity(a: string, b: string): number {
+  if (a === b) {
+    return 1;
+  }
+
+  let previous = Array.from({ length: b.length + 1 }, (_, index) => index);
+  for (let i = 1; i <= a.length; i += 1) {
+    const current = [i];
+    for (let j = 1; j <= b.length; j += 1) {
+      current[j] = Math.min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + (a[i - 1] === b[j - 1] ? 0 : 1));
+    }
+    previous = current;
+  }
+
+  return 1 - previous[b.length] / Math.max(a.length, b.length);
+}
+
+function jaccard(a: Set<string>, b: Set<string>): number {
+  const shared = Array.from(a).filter((entry) => b.has(entry)).length;
+  const union = a.size + b.size - shared;
+  return union === 0 ? 1 : shared / union;
+}
+
+function words(text: string): Set<string> {
+  return new Set(text.toLowerCase().match(/[a-z0-9]+/g) ?? []);
+}
+
+/** A signature with the symbol's own name taken out, so `loadUser(id: string)` equals `fetchUser(id: string)`. */
+function anonymousSignature(symbol: SymbolNode): string {
+  const name = symbol.name.replace(/[$]/g, "\\$&");
+  return (symbol.signature ?? "").replace(new RegExp(`(?<![\\w$])${name}(?![\\w$])`, "g"), "").trim();
+}
+
+interface Candidate {
+  symbol: SymbolNode;
+  file: FileGraph;
+}
+
+/**
+ * Similarity of each signal present on at least one side: signatures without the names (edit distance),
+ * doc words, members, and the import targets of the declaring files (Jaccard), and the names themselves.
+ */
+function similaritySignals(removed: Candidate, added: Candidate): Partial<Record<SimilaritySignal, number>> {
+  const signals: Partial<Record<SimilaritySignal, number>> = {};
+  const [oldSignature, newSignature] = [anonymousSignature(removed.symbol), anonymousSignature(added.symbol)];
+  if (oldSignature || newSignature) {
+    signals.signature = editSimilarity(oldSignature, newSignature);
+  }
+  if (removed.symbol.doc || added.symbol.doc) {
+    signals.doc = removed.symbol.doc && added.symbol.doc ? jaccard(words(removed.symbol.doc), words(added.symbol.doc)) : 0;
+  }
+  if (removed.symbol.members?.length || added.symbol.members?.length) {
+    signals.members = jaccard(new Set(removed.symbol.members), new Set(added.symbol.members));
+  }
+  const [oldImports, newImports] = [importTargets(removed.file), importTargets(added.file)];
+  if (oldImports.size > 0 || newImports.size > 0) {
+    signals.imports = jaccard(oldImports, newImports);
+  }
+  signals.name = editSimilarity(removed.symbol.name.toLowerCase(), added.symbol.name.toLowerCase());
+  return signals;
+}
+
+/** Weighted mean of the signals; symbols without a signature, doc, or members are never paired. */
+function similarityScore(signals: Partial<Record<SimilaritySignal, number>>): number {
+  if (signals.signature === undefined && signals.doc === undefined && signals.members === undefined) {
+    return 0;
+  }
+
+  const entries = Object.entries(signals) as [SimilaritySignal, number][];
+  const weight = entries.reduce((sum, [signal]) => sum + SIGNAL_WEIGHTS[signal], 0);
+  return entries.reduce((sum, [signal, value]) => sum + SIGNAL_WEIGHTS[signal] * value, 0) / weight;
+}
+
+function round(value: number): number {
+  return Math.round(value * 100) / 100;
+}
+
+/**
+ * Pairs removed and added symbols of the same kind whose similarity reaches `threshold`, best pairs first,
+ * and takes the pairs out of `removed` and `added`.
+ */
+function matchRenames(
+  removed: SymbolChange[],
+  added: SymbolChange[],
+  lookup: (change: SymbolChange, side: "before" | "after") => Candidate | undefined,
+  threshold: number
+): SymbolChange[] {
+  const pairs = removed.flatMap((removal) =>
+    added
+      .filter((addition) => addition.kind === removal.kind)
+      .flatMap((addition) => {
+        const [previous, next] = [lookup(removal, "before"), lookup(addition, "after")];
+        if (!previous || !next) {
+          return [];
+        }
+        const signals = similaritySignals(previous, next);
+        const score = similarityScore(signals);
+        return score >= threshold ? [{ removal, addition, signals, score }] : [];
+      })
+  );
+
+  const renamed: SymbolChange[] = [];
+  for (const pair of pairs.sort((a, b) => b.score - a.score)) {
+    if (!removed.includes(pair.removal) || !added.includes(pair.addition)) {
+      continue;
+    }
+
+    removed.splice(removed.indexOf(pair.removal), 1);
+    added.splice(added.indexOf(pair.addition), 1);
+    const evidence = Object.fromEntries(Object.entries(pair.signals).map(([signal, value]) => [signal, round(value)]));
+    renamed.push({
+      ...pair.addition,
+      change: "renamed",
+      previousName: pair.removal.name,
+      ...(pair.removal.file !== pair.addition.file ? { from: pair.removal.file } : {}),
+      before: pair.removal.before,
+      score: round(pair.score),
+      evidence,
+    });
+  }
+
+  return renamed;
+}
+
+/**
+ * Compares two graphs. Symbols are matched by kind and name within a file; a symbol that disappears
+ * from one file and appears in another with the same kind and name, such as after a file rename, is
+ * reported once as moved instead of as a removal plus an addition. Remaining removals and additions of
+ * the same kind are then paired by similarity (signature, doc, members, the declaring file's imports,
+ * and the name) and reported as renamed when the score reaches `renameThreshold`. Locations are ignored.
+ */
+export function diffGraphs(before: CodeGraph, after: CodeGraph, options: DiffOptions = {}): GraphDiff {
+  const beforeFiles = new Map(before.files.map((file) => [file.path, file]));
+  const afterFiles = new Map(after.files.map((file) => [file.path, file]));
+  const added: SymbolChange[] = [];
+  const removed: SymbolChange[] = [];
+  const changed: SymbolChange[] = [];
+  const imports: ImportChange[] = [];
+
+  for (const file of after.files) {
+    const previous = beforeFiles.get(file.path);
+    const previousSymbols = new Map((previous?.symbols ?? []).map((symbol) => [symbolKey(symbol), symbol]));
+
+    for (const symbol of file.symbols) {
+      const match = previousSymbols.get(symbolKey(symbol));
+      if (match) {
+        changed.push(...compareSymbols(file.path, match, symbol));
+      } else {
+        added.push({ change: "added", name: symbol.name, kind: symbol.kind, file: file.path, after: symbol.signature });
+      }
+    }
+
+    if (previous) {
+      const oldTargets = importTargets(previous);
+      const newTargets = importTargets(file);
+      const addedImports = Array.from(newTargets).filter((target) => !oldTargets.has(target));
+      const removedImports = Array.from(oldTargets).filter((target) => !newTargets.has(target));
+      if (addedImports.length > 0 || removedImports.length > 0) {
+        imports.push({ file: file.path, added: addedImports, removed: removedImports });
+      }
+    }
+  }
+
+  for (const file of before.files) {
+    const current = afterFiles.get(file.path);
+    const currentSymbols = new Set((current?.symbols ?? []).map(symbolKey));
+    for (const symbol of file.symbols.filter((candidate) => !currentSymbols.has(symbolKey(candidate)))) {
+      removed.push({ change: "removed", name: symbol.name, kind: symbol.kind, file: file.path, before: symbol.signature });
+    }
+  }
+
+  const moved: SymbolChange[] = [];
+  for (const removal of [...removed]) {
+    const index = added.findIndex((addition) => addition.kind === removal.kind && addition.name === removal.name);
+    if (index === -1) {
+      continue;
+    }
+
+    const [addition] = added.splice(index, 1);
+    removed.splice(removed.indexOf(removal), 1);
+    moved.push({ ...addition, change: "moved", from: removal.file, before: removal.before });
+  }
+
+  const candidate = (change: SymbolChange, side: "before" | "after"): Candidate | undefined => {
+    const file = (side === "before" ? beforeFiles : afterFiles).get(change.file);
+    const symbol = file?.symbols.find((entry) => entry.kind === change.kind && entry.name === change.name);
+    return file && symbol ? { symbol, file } : undefined;
+  };
+  const renamed = matchRenames(removed, added, candidate, options.renameThreshold ?? DEFAULT_RENAME_THRESHOLD);
+
+  return {
+    addedFiles: after.files.filter((file) => !beforeFiles.has(file.path)).map((file) => file.path),
+    removedFiles: before.files.filter((file) => !afterFiles.has(file.path)).map((file) => file.path),
+    symbols: [...added, ...removed, ...moved, ...renamed, ...changed],
+    imports,
+  };
+}
+
+const CHANGE_MARKERS: Record<SymbolChangeKind, string> = {
+  added: "+",
+  removed: "-",
+  moved: ">",
+  renamed: ">",
+  "signature-changed": "~",
+  "export-changed": "~",
+};
+
+/** Renders a diff as one line per change, prefixed with `+`, `-`, `>` (moved or renamed) or `~` (changed). */
+export function formatGraphDiff(diff: GraphDiff): string {
+  const lines = [
+    ...diff.addedFiles.map((file) => `+ file ${file}`),
+    ...diff.removedFiles.map((file) => `- file ${file}`),
+    ...diff.symbols.map((change) => {
+      const where = change.from ? `${change.from} -> ${change.file}` : change.file;
+      const modified = change.change === "signature-changed" || change.change === "export-changed";
+      const renamed = change.change === "renamed" ? ` renamed from ${change.previousName} (score ${change.score})` : "";
+      const detail = modified ? ` ${change.change}: ${change.before} -> ${change.after}` : renamed;
+      return `${CHANGE_MARKERS[change.change]} ${change.kind} ${change.name} (${where})${detail}`;
+    }),
+    ...diff.imports.flatMap((change) => [
+      ...change.added.map((target) => `+ import ${change.file} -> ${target}`),
+      ...change.removed.map((target) => `- import ${change.file} -> ${target}`),
+    ]),
+  ];
+
+  return lines.length > 0 ? `${lines.join("\n")}\n` : "No differences\n";
+}
+
+export type DiffFormat = "text" | "yaml" | "json";
+
+export function renderGraphDiff(diff: GraphDiff, format: DiffFormat = "text"): string {
+  switch (format) {
+    case "yaml":
+      return YAML.stringify(diff);
+    case "json":
+      return `${JSON.stringify(diff, null, 2)}\n`;
+    default:
+      return formatGraphDiff(diff);
+  }
+}
@@ -0,0 +1,34 @@
+import { firstSentence } from "./summaries";
+
+export interface DocPolicy {
+  /** Truncate docs longer than this many characters, ending them with an ellipsis. */
+  maxChars?: number;
+  /** Keep only the first sentence. */
+  firstSentenceOnly?: boolean;
+  /** Drop block tags such as `@param` and `@returns`, and everything after the first one. */
+  stripTags?: boolean;
+}
+
+const BLOCK_TAG = /^\s*@\w+/m;
+
+/**
+ * Shortens a JSDoc text according to `policy`. Any policy setting also collapses runs of whitespace,
+ * including newlines and tabs, into single spaces. Returns undefined when nothing is left.
+ */
+export function applyDocPolicy(doc: string | undefined, policy?: DocPolicy): string | undefined {
+  if (doc === undefined || !policy || (!policy.maxChars && !policy.firstSentenceOnly && !policy.stripTags)) {
+    return doc;
+  }
+
+  const tagIndex = policy.stripTags ? doc.search(BLOCK_TAG) : -1;
+  let text = (tagIndex === -1 ? doc : doc.slice(0, tagIndex)).replace(/\s+/g, " ").trim();
+
+  if (policy.firstSentenceOnly) {
+    text = firstSentence(text);
+  }
+  if (policy.maxChars && text.length > policy.maxChars) {
+    text = `${text.slice(0, Math.max(0, policy.maxChars - 3)).trimEnd()}...`;
+  }
+
+  return text || undefined;
+}
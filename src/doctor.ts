@@ -0,0 +1,101 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+import ts from "typescript";
+import { DEFAULT_CONFIG_FILE, loadConfigFile, validateConfig } from "./config";
+import { parseSource } from "./parser";
+
+export type CheckStatus = "pass" | "warn" | "fail";
+
+export interface DoctorCheck {
+  name: string;
+  status: CheckStatus;
+  detail: string;
+}
+
+const MINIMUM_NODE_MAJOR = 18;
+const LOW_MEMORY_BYTES = 512 * 1024 * 1024;
+
+const SMOKE_TEST_SOURCE = `import { readFileSync } from "fs";
+/** Greets someone. */
+export function greet(name: string): string {
+  return name ? \`hi \${name}\` : "hi";
+}
+export class Greeter {}
+`;
+
+function checkNode(): DoctorCheck {
+  const major = Number(process.versions.node.split(".")[0]);
+  return major >= MINIMUM_NODE_MAJOR
+    ? { name: "node", status: "pass", detail: `Node.js ${process.versions.node}` }
+    : { name: "node", status: "fail", detail: `Node.js ${process.versions.node} is older than ${MINIMUM_NODE_MAJOR}` };
+}
+
+function checkModule(name: string): DoctorCheck {
+  try {
+    const manifestPath = require.resolve(`${name}/package.json`);
+    const { version } = JSON.parse(fs.readFileSync(manifestPath, "utf8"));
+    return { name, status: "pass", detail: `${name} ${version}` };
+  } catch {
+    return { name, status: "fail", detail: `${name} cannot be resolved; reinstall ts-yaml-code-graph` };
+  }
+}
+
+function checkWritable(directory: string): DoctorCheck {
+  try {
+    fs.accessSync(directory, fs.constants.W_OK);
+    return { name: "write-permission", status: "pass", detail: `${directory} is writable` };
+  } catch {
+    return { name: "write-permission", status: "fail", detail: `${directory} is not writable` };
+  }
+}
+
+function checkConfig(directory: string): DoctorCheck {
+  const configPath = path.join(directory, DEFAULT_CONFIG_FILE);
+  if (!fs.existsSync(configPath)) {
+    return { name: "config", status: "pass", detail: `no ${DEFAULT_CONFIG_FILE}; defaults apply` };
+  }
+
+  try {
+    validateConfig(loadConfigFile(configPath));
+    return { name: "config", status: "pass", detail: `${DEFAULT_CONFIG_FILE} is valid` };
+  } catch (error) {
+    return { name: "config", status: "fail", detail: (error as Error).message };
+  }
+}
+
+function checkMemory(): DoctorCheck {
+  const free = os.freemem();
+  const detail = `${Math.round(free / (1024 * 1024))} MiB free`;
+  return free < LOW_MEMORY_BYTES
+    ? { name: "memory", status: "warn", detail: `${detail}; large projects may need more` }
+    : { name: "memory", status: "pass", detail };
+}
+
+function checkSmokeTest(): DoctorCheck {
+  try {
+    const file = parseSource(SMOKE_TEST_SOURCE, "smoke.ts", ".");
+    const ok = file.imports.length === 1 && file.symbols.length === 2 && !file.parseErrors;
+    return ok
+      ? { name: "smoke-test", status: "pass", detail: `parsed a sample file with TypeScript ${ts.version}` }
+      : { name: "smoke-test", status: "fail", detail: "the sample file produced an unexpected graph" };
+  } catch (error) {
+    return { name: "smoke-test", status: "fail", detail: (error as Error).message };
+  }
+}
+
+/**
+ * Checks the runtime, dependencies, and working directory ycg needs.
+ */
+export function runDoctor(directory: string): DoctorCheck[] {
+  const resolved = path.resolve(directory);
+  return [
+    checkNode(),
+    checkModule("typescript"),
+    checkModule("yaml"),
+    checkWritable(resolved),
+    checkConfig(resolved),
+    checkMemory(),
+    checkSmokeTest(),
+  ];
+}
@@ -0,0 +1,37 @@
+import { FileGraph, ImportEdge } from "./types";
+
+/** Import kinds that can be filtered; `value` is a plain import, which carries no `kind`. */
+export type ImportEdgeKind = "value" | "type" | "side-effect" | "re-export";
+
+export const IMPORT_EDGE_KINDS: readonly ImportEdgeKind[] = ["value", "type", "side-effect", "re-export"];
+
+export interface ImportFilterOptions {
+  /** Drop imports of these kinds. */
+  excludeKinds?: ImportEdgeKind[];
+  /** Drop imports that resolve to the importing file itself. */
+  dropSelfImports?: boolean;
+}
+
+function edgeKind(edge: ImportEdge): ImportEdgeKind {
+  return edge.kind ?? "value";
+}
+
+/** Removes the imports matching `options` from every file; symbols and files are left as they are. */
+export function filterImports(files: FileGraph[], options: ImportFilterOptions): FileGraph[] {
+  const excluded = new Set(options.excludeKinds ?? []);
+  if (excluded.size === 0 && !options.dropSelfImports) {
+    return files;
+  }
+
+  const kept = (file: FileGraph, edge: ImportEdge) => !excluded.has(edgeKind(edge)) && !(options.dropSelfImports && edge.resolved === file.path);
+  return files.map((file) => ({ ...file, imports: file.imports.filter((edge) => kept(file, edge)) }));
+}
+
+/**
+ * Removes files that no longer say anything: no symbols, no imports, and no file of the graph importing
+ * them. Files that still declare symbols are always kept.
+ */
+export function pruneOrphans(files: FileGraph[]): FileGraph[] {
+  const imported = new Set(files.flatMap((file) => file.imports.map((edge) => edge.resolved).filter((target): target is string => Boolean(target))));
+  return files.filter((file) => file.symbols.length > 0 || file.imports.length > 0 || imported.has(file.path));
+}
@@ -0,0 +1,41 @@
+import fs from "fs";
+
+export type SourceText = { kind: "text"; content: string; replacements: number } | { kind: "binary" };
+
+const SAMPLE_BYTES = 8000;
+const MAX_REPLACEMENT_RATIO = 0.1;
+const REPLACEMENT_CHARACTER = "\uFFFD";
+
+function countReplacements(text: string): number {
+  let count = 0;
+  for (const character of text) {
+    if (character === REPLACEMENT_CHARACTER) {
+      count += 1;
+    }
+  }
+
+  return count;
+}
+
+/**
+ * Reads a source file as UTF-8. Invalid sequences are decoded lossily and counted; files containing NUL
+ * bytes or dominated by invalid sequences are classified as binary.
+ */
+export function readSourceText(filePath: string): SourceText {
+  const buffer = fs.readFileSync(filePath);
+  if (buffer.subarray(0, SAMPLE_BYTES).includes(0)) {
+    return { kind: "binary" };
+  }
+
+  const content = buffer.toString("utf8");
+  if (Buffer.from(content, "utf8").equals(buffer)) {
+    return { kind: "text", content, replacements: 0 };
+  }
+
+  const replacements = countReplacements(content);
+  if (content.length > 0 && replacements / content.length > MAX_REPLACEMENT_RATIO) {
+    return { kind: "binary" };
+  }
+
+  return { kind: "text", content, replacements };
+}
@@ -0,0 +1,77 @@
+import path from "path";
+import { GraphOptions, parseFiles } from "./graph";
+import { relativePosixPath, toPosixPath } from "./paths";
+import { scanSourceFiles, SkipReason } from "./scanner";
+import { isTestFile } from "./testFiles";
+import { SymbolKind } from "./types";
+import { serializeGraph, SerializeOptions } from "./yamlSerializer";
+
+export interface GraphEstimate {
+  files: number;
+  sampledFiles: number;
+  skipped: Record<SkipReason, number>;
+  symbolsByKind: Partial<Record<SymbolKind, number>>;
+  imports: number;
+  outputTokens: number;
+}
+
+const DEFAULT_SAMPLE_RATIO = 0.05;
+
+/**
+ * Rough token count used for size estimates: about four characters per token.
+ */
+export function estimateTokens(text: string): number {
+  return Math.ceil(text.length / 4);
+}
+
+function pickSample(files: string[], ratio: number): string[] {
+  if (files.length === 0) {
+    return [];
+  }
+
+  const step = Math.max(1, Math.round(1 / ratio));
+  return files.filter((_, index) => index % step === 0);
+}
+
+/**
+ * Estimates the size of the graph for `root` without generating it: files are scanned and filtered,
+ * a deterministic sample of them is fully processed, and the sample's counts are scaled to all files.
+ */
+export function estimateGraph(
+  root: string,
+  options: GraphOptions & SerializeOptions = {},
+  sampleRatio = DEFAULT_SAMPLE_RATIO
+): GraphEstimate {
+  const normalizedRoot = path.resolve(root);
+  const scan = scanSourceFiles(normalizedRoot, options.extensions);
+  const files = options.tests === "exclude" ? scan.files.filter((file) => !isTestFile(relativePosixPath(normalizedRoot, file))) : scan.files;
+  const sample = pickSample(files, sampleRatio);
+  const sampledFiles = parseFiles(sample, normalizedRoot, options);
+  const scale = sampledFiles.length > 0 ? files.length / sampledFiles.length : 0;
+
+  const symbolsByKind: Partial<Record<SymbolKind, number>> = {};
+  for (const symbol of sampledFiles.flatMap((file) => file.symbols)) {
+    symbolsByKind[symbol.kind] = (symbolsByKind[symbol.kind] ?? 0) + 1;
+  }
+  for (const kind of Object.keys(symbolsByKind) as SymbolKind[]) {
+    symbolsByKind[kind] = Math.round((symbolsByKind[kind] ?? 0) * scale);
+  }
+
+  const sampleOutput = serializeGraph(
+    { root: toPosixPath(normalizedRoot), generatedAt: new Date().toISOString(), files: sampledFiles },
+    options
+  );
+
+  return {
+    files: files.length,
+    sampledFiles: sampledFiles.length,
+    skipped: {
+      "ignored-directory": scan.skipped.filter((entry) => entry.reason === "ignored-directory").length,
+      ycgignore: scan.skipped.filter((entry) => entry.reason === "ycgignore").length,
+      extension: scan.skipped.filter((entry) => entry.reason === "extension").length,
+    },
+    symbolsByKind,
+    imports: Math.round(sampledFiles.reduce((total, file) => total + file.imports.length, 0) * scale),
+    outputTokens: Math.round(estimateTokens(sampleOutput) * scale),
+  };
+}
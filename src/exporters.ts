@@ -0,0 +1,147 @@
+import { resolveStyles, ResolvedStyle, ResolvedStyles } from "./styles";
+import { CodeGraph, FileGraph } from "./types";
+import { edgeStyleKind, nodeStyleKind, VisualizationOptions } from "./visualizer";
+
+interface ExportNode {
+  id: string;
+  label: string;
+  kind: string;
+  style: ResolvedStyle;
+  /** File whose cluster contains the node. */
+  parent?: string;
+}
+
+interface ExportEdge {
+  source: string;
+  target: string;
+  label: "imports" | "contains";
+  style: ResolvedStyle;
+}
+
+const DOT_SHAPES: Record<ResolvedStyle["shape"], string> = { circle: "ellipse", box: "box", diamond: "diamond" };
+
+function symbolId(file: FileGraph, name: string): string {
+  return `${file.path}#${name}`;
+}
+
+/**
+ * Flattens a graph into file, symbol and external-module nodes with import and containment edges,
+ * leaving out everything whose style is hidden. Like the HTML view, a file imports each target once,
+ * however many import statements name it.
+ */
+function collectElements(graph: CodeGraph, styles: ResolvedStyles): { nodes: ExportNode[]; edges: ExportEdge[] } {
+  const nodes: ExportNode[] = [];
+  const edges: ExportEdge[] = [];
+  const files = graph.files.filter((file) => !styles.nodes[nodeStyleKind(file)].hidden);
+  const visibleFiles = new Set(files.map((file) => file.path));
+  const externals = new Set<string>();
+  const linked = new Set<string>();
+
+  for (const file of files) {
+    nodes.push({ id: file.path, label: file.path, kind: nodeStyleKind(file), style: styles.nodes[nodeStyleKind(file)] });
+
+    if (!styles.nodes.symbol.hidden) {
+      const seen = new Set<string>();
+      for (const symbol of file.symbols) {
+        const id = symbolId(file, symbol.name);
+        if (seen.has(id)) {
+          continue;
+        }
+
+        seen.add(id);
+        nodes.push({ id, label: symbol.name, kind: symbol.kind, style: styles.nodes.symbol, parent: file.path });
+      }
+    }
+
+    for (const edge of file.imports) {
+      const style = styles.edges[edgeStyleKind(edge)];
+      const target = edge.resolved ?? edge.from;
+      const key = `${file.path}__${target}`;
+      if (linked.has(key) || style.hidden || (edge.resolved ? !visibleFiles.has(target) : styles.nodes.external.hidden)) {
+        continue;
+      }
+
+      linked.add(key);
+
+      if (!edge.resolved && !externals.has(target)) {
+        externals.add(target);
+        nodes.push({ id: target, label: target, kind: "external", style: styles.nodes.external });
+      }
+      edges.push({ source: file.path, target, label: "imports", style });
+    }
+  }
+
+  return { nodes, edges };
+}
+
+function quoteDot(value: string): string {
+  return `"${value.replace(/\\/g, "\\\\").replace(/"/g, '\\"').replace(/\n/g, "\\n")}"`;
+}
+
+function dotNode(node: ExportNode, indent: string): string {
+  const { color, shape, line } = node.style;
+  return `${indent}${quoteDot(node.id)} [label=${quoteDot(node.label)}, kind=${quoteDot(node.kind)}, color=${quoteDot(color)}, shape=${DOT_SHAPES[shape]}, style=${line}];`;
+}
+
+/**
+ * Renders the graph as Graphviz DOT. Each file becomes a cluster holding the file node and its symbols;
+ * import edges connect file nodes to each other and to external modules.
+ */
+export function renderDot(graph: CodeGraph, options: VisualizationOptions = {}): string {
+  const { nodes, edges } = collectElements(graph, resolveStyles(options.styles, options.onWarning));
+  const lines = [`digraph ${quoteDot(options.title ?? "code graph")} {`, "  rankdir=LR;"];
+
+  nodes
+    .filter((node) => node.kind !== "external" && node.parent === undefined)
+    .forEach((fileNode, index) => {
+      lines.push(`  subgraph cluster_${index} {`, `    label=${quoteDot(fileNode.label)};`, dotNode(fileNode, "    "));
+      nodes.filter((node) => node.parent === fileNode.id).forEach((node) => lines.push(dotNode(node, "    ")));
+      lines.push("  }");
+    });
+  nodes.filter((node) => node.kind === "external").forEach((node) => lines.push(dotNode(node, "  ")));
+
+  for (const edge of edges) {
+    lines.push(`  ${quoteDot(edge.source)} -> ${quoteDot(edge.target)} [label=${edge.label}, color=${quoteDot(edge.style.color)}, style=${edge.style.line}];`);
+  }
+
+  lines.push("}");
+  return `${lines.join("\n")}\n`;
+}
+
+function escapeXml(value: string): string {
+  return value.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;").replace(/"/g, "&quot;").replace(/'/g, "&apos;");
+}
+
+/**
+ * Renders the graph as GraphML for tools such as Gephi or yEd. Nodes carry label, kind, color and shape
+ * attributes; edges are typed `imports`, or `contains` from a file to its symbols.
+ */
+export function renderGraphMl(graph: CodeGraph, options: VisualizationOptions = {}): string {
+  const { nodes, edges } = collectElements(graph, resolveStyles(options.styles, options.onWarning));
+  const containment: ExportEdge[] = nodes
+    .filter((node) => node.parent !== undefined)
+    .map((node) => ({ source: node.parent as string, target: node.id, label: "contains", style: node.style }));
+  const data = (key: string, value: string) => `<data key="${key}">${escapeXml(value)}</data>`;
+
+  const lines = [
+    '<?xml version="1.0" encoding="UTF-8"?>',
+    '<graphml xmlns="http://graphml.graphdrawing.org/xmlns">',
+    '  <key id="label" for="node" attr.name="label" attr.type="string"/>',
+    '  <key id="kind" for="node" attr.name="kind" attr.type="string"/>',
+    '  <key id="color" for="all" attr.name="color" attr.type="string"/>',
+    '  <key id="shape" for="node" attr.name="shape" attr.type="string"/>',
+    '  <key id="type" for="edge" attr.name="type" attr.type="string"/>',
+    `  <graph id="${escapeXml(options.title ?? "code graph")}" edgedefault="directed">`,
+    ...nodes.map(
+      (node) =>
+        `    <node id="${escapeXml(node.id)}">${data("label", node.label)}${data("kind", node.kind)}${data("color", node.style.color)}${data("shape", node.style.shape)}</node>`
+    ),
+    ...[...containment, ...edges].map(
+      (edge) => `    <edge source="${escapeXml(edge.source)}" target="${escapeXml(edge.target)}">${data("type", edge.label)}${data("color", edge.style.color)}</edge>`
+    ),
+    "  </graph>",
+    "</graphml>",
+  ];
+
+  return `${lines.join("\n")}\n`;
+}
@@ -0,0 +1,38 @@
+import { CodeGraph, CommonExternal, ImportEdge } from "./types";
+
+function isExternal(edge: ImportEdge): boolean {
+  return !edge.resolved && !edge.from.startsWith(".");
+}
+
+/**
+ * Removes import edges to external modules imported by more than `threshold` distinct files and lists
+ * those modules once in `commonExternals` with their importer counts, most imported first.
+ */
+export function suppressCommonExternals(graph: CodeGraph, threshold: number): CodeGraph {
+  const importers = new Map<string, Set<string>>();
+  for (const file of graph.files) {
+    for (const edge of file.imports.filter(isExternal)) {
+      const files = importers.get(edge.from) ?? new Set<string>();
+      files.add(file.path);
+      importers.set(edge.from, files);
+    }
+  }
+
+  const commonExternals: CommonExternal[] = Array.from(importers.entries())
+    .filter(([, files]) => files.size > threshold)
+    .map(([module, files]) => ({ module, references: files.size }))
+    .sort((a, b) => b.references - a.references || a.module.localeCompare(b.module));
+  if (commonExternals.length === 0) {
+    return graph;
+  }
+
+  const suppressed = new Set(commonExternals.map((external) => external.module));
+  return {
+    ...graph,
+    files: graph.files.map((file) => ({
+      ...file,
+      imports: file.imports.filter((edge) => !(isExternal(edge) && suppressed.has(edge.from))),
+    })),
+    commonExternals,
+  };
+}
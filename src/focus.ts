@@ -0,0 +1,57 @@
+import { matchesAnyGlob } from "./glob";
+import { CodeGraph, FileGraph } from "./types";
+
+export interface FocusOptions {
+  /** Glob patterns selecting the files the neighborhood is seeded with. */
+  paths: string[];
+  /** Number of import hops to expand in either direction (default: 1). */
+  depth?: number;
+}
+
+function buildNeighbors(files: FileGraph[]): Map<string, Set<string>> {
+  const neighbors = new Map<string, Set<string>>(files.map((file) => [file.path, new Set<string>()]));
+
+  for (const file of files) {
+    for (const edge of file.imports) {
+      if (edge.resolved && neighbors.has(edge.resolved)) {
+        neighbors.get(file.path)?.add(edge.resolved);
+        neighbors.get(edge.resolved)?.add(file.path);
+      }
+    }
+  }
+
+  return neighbors;
+}
+
+/**
+ * Keeps the files matching `paths` and every file within `depth` import hops of them, in either
+ * direction. Seed files are kept whole; files reached through expansion are kept as stubs without
+ * symbols, so the result shows which files the selection depends on and is used by.
+ */
+export function focusGraph(graph: CodeGraph, options: FocusOptions): CodeGraph {
+  const seeds = graph.files.filter((file) => matchesAnyGlob(file.path, options.paths)).map((file) => file.path);
+  const neighbors = buildNeighbors(graph.files);
+  const reached = new Set(seeds);
+  let frontier = seeds;
+
+  for (let level = 0; level < (options.depth ?? 1); level += 1) {
+    const next: string[] = [];
+    for (const filePath of frontier) {
+      for (const neighbor of neighbors.get(filePath) ?? []) {
+        if (!reached.has(neighbor)) {
+          reached.add(neighbor);
+          next.push(neighbor);
+        }
+      }
+    }
+    frontier = next;
+  }
+
+  const seedSet = new Set(seeds);
+  return {
+    ...graph,
+    files: graph.files
+      .filter((file) => reached.has(file.path))
+      .map((file) => (seedSet.has(file.path) ? file : { ...file, symbols: [], stub: true })),
+  };
+}
@@ -0,0 +1,32 @@
+/**
+ * Converts a glob pattern to a regular expression matched against forward-slash paths.
+ * Supports `**` (any number of directories), `*` (anything but `/`), and `?`; a pattern ending in `/`
+ * matches everything below that directory.
+ */
+export function globToRegExp(pattern: string): RegExp {
+  const normalized = pattern.endsWith("/") ? `${pattern}**` : pattern;
+  let source = "";
+
+  for (let i = 0; i < normalized.length; i += 1) {
+    const character = normalized[i];
+    if (character === "*") {
+      if (normalized[i + 1] === "*") {
+        const followedBySlash = normalized[i + 2] === "/";
+        source += followedBySlash ? "(?:.*/)?" : ".*";
+        i += followedBySlash ? 2 : 1;
+      } else {
+        source += "[^/]*";
+      }
+    } else if (character === "?") {
+      source += "[^/]";
+    } else {
+      source += character.replace(/[.+^${}()|[\]\\]/g, "\\$&");
+    }
+  }
+
+  return new RegExp(`^${source}$`);
+}
+
+export function matchesAnyGlob(filePath: string, patterns: string[]): boolean {
+  return patterns.some((pattern) => globToRegExp(pattern).test(filePath));
+}
@@ -1,20 +1,289 @@
 import path from "path";
-import { collectSourceFiles } from "./scanner";
-import { parseSourceFile } from "./parser";
-import { CodeGraph } from "./types";
+import { collapseBarrels } from "./barrels";
+import { hashContent, ParseCache } from "./cache";
+import { addFileDependencies } from "./dependencies";
+import { DeclarationMerge, DeclarationMode, DEFAULT_DECLARATION_REWRITES, mergeDeclarationFiles } from "./declarations";
+import { Diagnostic, DiagnosticCodes } from "./diagnostics";
+import { filterImports, ImportEdgeKind, pruneOrphans } from "./edges";
+import { isInIgnoredDirectory, scanSourceFiles, SkipReason } from "./scanner";
+import { readSourceText } from "./encoding";
+import { suppressCommonExternals } from "./externals";
+import { focusGraph } from "./focus";
+import { resolveImplementations } from "./implementations";
+import { assignPackages } from "./packages";
+import { parseSource, ParserOptions } from "./parser";
+import { relativePosixPath, toPosixPath } from "./paths";
+import { createImportResolver, resolveImports } from "./resolver";
+import { addReverseImports } from "./reverse";
+import { isTestFile, tagTestFiles, TestFileMode } from "./testFiles";
+import { CodeGraph, FileGraph, NodeRole, SymbolKind, SymbolNode } from "./types";
 
-export interface GraphOptions {
+export type RemovalStage = "scan" | "declarations" | "barrels" | "symbols" | "orphans" | "focus";
+
+/** A file or symbol left out of the graph, and the stage and rule responsible. */
+export interface Removal {
+  stage: RemovalStage;
+  /** What removed it, such as `.ycgignore`, `--exclude-kinds`, or the implementation a declaration duplicates. */
+  rule: string;
+  file: string;
+  /** Set when a single symbol was removed rather than the whole file. */
+  symbol?: string;
+  kind?: SymbolKind;
+}
+
+export interface GraphOptions extends ParserOptions {
   extensions?: string[];
+  /** Whether test files are kept, left out, or kept and marked `test: true` (default: include). */
+  tests?: TestFileMode;
+  /** How `.d.ts` files that duplicate an implementation file are handled (default: merge). */
+  declarationFiles?: DeclarationMode;
+  declarationRewrites?: Record<string, string>;
+  /** Called for every declaration file merged into its implementation. */
+  onDeclarationMerge?: (merge: DeclarationMerge) => void;
+  /** tsconfig.json whose `paths` and `baseUrl` resolve aliased imports (default: <root>/tsconfig.json). */
+  tsconfig?: string;
+  /** Restrict the graph to files matching these globs and their import neighborhood. */
+  focusPaths?: string[];
+  focusDepth?: number;
+  /** Keep only symbols with one of these React roles. */
+  keepRoles?: NodeRole[];
+  /** Keep only symbols of these kinds. */
+  includeKinds?: SymbolKind[];
+  /** Drop symbols of these kinds; applied after `includeKinds`. */
+  excludeKinds?: SymbolKind[];
+  /** Keep only exported symbols. */
+  publicOnly?: boolean;
+  /** Drop imports of these kinds. */
+  excludeImports?: ImportEdgeKind[];
+  /** Drop imports that resolve to the importing file itself. */
+  dropSelfImports?: boolean;
+  /** Remove files left with no symbols, no imports, and no importers after filtering. */
+  pruneOrphans?: boolean;
+  /** Replace edges to externals imported by more than this many files with one `commonExternals` entry. */
+  externalNoiseThreshold?: number;
+  /** List the importers of each file in `importedBy`. */
+  reverseImports?: boolean;
+  /** List the files each file imports in `dependsOn`. */
+  fileDependencies?: boolean;
+  /** Remove files that only re-export other modules and point their importers at the defining files. */
+  collapseBarrels?: boolean;
+  /** Record the workspace package of each file, from the nearest `package.json`. */
+  packages?: boolean;
+  /** Reuse parses of files whose content is unchanged since the cache was written. */
+  cache?: ParseCache;
+  /** Called for every problem that does not stop graph generation, as soon as it is found. */
+  onDiagnostic?: (diagnostic: Diagnostic) => void;
+  /** Called for every file or symbol a pipeline stage leaves out of the graph. */
+  onRemoval?: (removal: Removal) => void;
+}
+
+const SKIP_RULES: Record<SkipReason, string> = {
+  "ignored-directory": "ignored directory",
+  ycgignore: ".ycgignore",
+  extension: "--extensions",
+};
+
+export function parseFiles(files: string[], root: string, options: GraphOptions): FileGraph[] {
+  const parsed: FileGraph[] = [];
+
+  for (const file of files) {
+    const source = readSourceText(file);
+    const relativePath = relativePosixPath(root, file);
+
+    if (source.kind === "binary") {
+      options.onDiagnostic?.({
+        level: "warning",
+        code: DiagnosticCodes.binaryFile,
+        file: relativePath,
+        message: "looks like a binary file and was excluded from the graph",
+      });
+      options.onRemoval?.({ stage: "scan", rule: "binary file", file: relativePath });
+      continue;
+    }
+
+    if (source.replacements > 0) {
+      options.onDiagnostic?.({
+        level: "warning",
+        code: DiagnosticCodes.invalidUtf8,
+        file: relativePath,
+        message: `is not valid UTF-8; ${source.replacements} invalid sequence(s) were replaced before parsing`,
+      });
+    }
+
+    const hash = options.cache ? hashContent(source.content) : "";
+    const cached = options.cache?.lookup(relativePath, hash);
+    const fileGraph = cached ?? parseSource(source.content, file, root, options);
+    if (!cached) {
+      options.cache?.store(relativePath, hash, fileGraph);
+    }
+
+    parsed.push(fileGraph);
+  }
+
+  return parsed;
+}
+
+/** The filter option that drops `symbol`, if any. */
+function symbolFilter(symbol: SymbolNode, options: GraphOptions): string | undefined {
+  const { keepRoles, includeKinds, excludeKinds } = options;
+  if (options.publicOnly && !symbol.exported) {
+    return "--public-only";
+  }
+  if (keepRoles?.length && (symbol.role === undefined || !keepRoles.includes(symbol.role))) {
+    return "--keep-role";
+  }
+  if (includeKinds?.length && !includeKinds.includes(symbol.kind)) {
+    return "--include-kinds";
+  }
+  return excludeKinds?.includes(symbol.kind) ? "--exclude-kinds" : undefined;
+}
+
+function filterSymbols(files: FileGraph[], options: GraphOptions): FileGraph[] {
+  if (!options.publicOnly && !options.keepRoles?.length && !options.includeKinds?.length && !options.excludeKinds?.length) {
+    return files;
+  }
+
+  return files.map((file) => ({
+    ...file,
+    symbols: file.symbols.filter((symbol) => {
+      const rule = symbolFilter(symbol, options);
+      if (rule) {
+        options.onRemoval?.({ stage: "symbols", rule, file: file.path, symbol: symbol.name, kind: symbol.kind });
+      }
+      return !rule;
+    }),
+  }));
+}
+
+/** Reports the files of `before` missing from `after`, and the symbols missing from files still present. */
+function reportRemovals(
+  before: FileGraph[],
+  after: FileGraph[],
+  stage: RemovalStage,
+  rule: (file: FileGraph) => string,
+  onRemoval?: (removal: Removal) => void
+): void {
+  if (!onRemoval) {
+    return;
+  }
+
+  const remaining = new Map(after.map((file) => [file.path, file]));
+  for (const file of before) {
+    const kept = remaining.get(file.path);
+    if (!kept) {
+      onRemoval({ stage, rule: rule(file), file: file.path });
+      continue;
+    }
+
+    for (const symbol of file.symbols) {
+      if (!kept.symbols.some((candidate) => candidate.name === symbol.name && candidate.kind === symbol.kind)) {
+        onRemoval({ stage, rule: rule(file), file: file.path, symbol: symbol.name, kind: symbol.kind });
+      }
+    }
+  }
+}
+
+function collapseBarrelFiles(files: FileGraph[], options: GraphOptions): FileGraph[] {
+  const collapsed = collapseBarrels(files);
+  if (collapsed.dropped > 0) {
+    options.onDiagnostic?.({
+      level: "warning",
+      code: DiagnosticCodes.barrelImportDropped,
+      message: `${collapsed.dropped} import(s) through ${collapsed.barrels.length} barrel file(s) could not be traced to a defining file and were dropped`,
+      hint: "the names may be re-exported from an external package or a file excluded from the scan",
+    });
+  }
+
+  for (const barrel of collapsed.barrels) {
+    options.onRemoval?.({ stage: "barrels", rule: "--collapse-barrels (re-exports only)", file: barrel });
+  }
+
+  return collapsed.files;
 }
 
 export function buildGraph(root: string, options: GraphOptions = {}): CodeGraph {
   const normalizedRoot = path.resolve(root);
-  const files = collectSourceFiles(normalizedRoot, options.extensions);
-  const parsedFiles = files.map((file) => parseSourceFile(file, normalizedRoot));
+  const mergeDeclarations = options.declarationFiles !== "keep";
+  const rewrites = options.declarationRewrites ?? DEFAULT_DECLARATION_REWRITES;
+  // Declarations under an otherwise ignored directory, such as `dist/`, are only scanned to be merged.
+  const scan = scanSourceFiles(normalizedRoot, options.extensions, mergeDeclarations ? Object.keys(rewrites) : []);
+  for (const entry of scan.skipped) {
+    options.onRemoval?.({ stage: "scan", rule: SKIP_RULES[entry.reason], file: relativePosixPath(normalizedRoot, entry.path) });
+  }
+
+  const sourceFiles = scan.files.filter((file) => {
+    const relativePath = relativePosixPath(normalizedRoot, file);
+    const excluded = options.tests === "exclude" && isTestFile(relativePath);
+    if (excluded) {
+      options.onRemoval?.({ stage: "scan", rule: "--tests exclude", file: relativePath });
+    }
+    return !excluded;
+  });
+
+  const parsed = parseFiles(sourceFiles, normalizedRoot, options);
+  const parsedFiles = options.tests === "tag" ? tagTestFiles(parsed) : parsed;
+  const implementations = new Map<string, string>();
+  const onDeclarationMerge = (merge: DeclarationMerge) => {
+    implementations.set(merge.declaration, merge.implementation);
+    options.onDeclarationMerge?.(merge);
+  };
+  const declaredFiles = mergeDeclarations ? mergeDeclarationFiles(parsedFiles, rewrites, onDeclarationMerge) : parsedFiles;
+  reportRemovals(parsedFiles, declaredFiles, "declarations", (file) => `duplicates ${implementations.get(file.path) ?? "an implementation file"}`, options.onRemoval);
+  const mergedFiles = declaredFiles.filter((file) => implementations.has(file.path) || !isInIgnoredDirectory(file.path));
+  reportRemovals(declaredFiles, mergedFiles, "scan", () => SKIP_RULES["ignored-directory"], options.onRemoval);
+  const resolvedFiles = resolveImports(mergedFiles, createImportResolver(normalizedRoot, mergedFiles, options.tsconfig));
+
+  const directFiles = options.collapseBarrels ? collapseBarrelFiles(resolvedFiles, options) : resolvedFiles;
+
+  const trimmedFiles = filterImports(directFiles, { excludeKinds: options.excludeImports, dropSelfImports: options.dropSelfImports });
 
-  return {
-    root: normalizedRoot,
+  const linkedFiles = options.implementations ? resolveImplementations(trimmedFiles) : trimmedFiles;
+  const filteredFiles = filterSymbols(linkedFiles, options);
+  const keptFiles = options.pruneOrphans ? pruneOrphans(filteredFiles) : filteredFiles;
+  reportRemovals(filteredFiles, keptFiles, "orphans", () => "--prune-orphans (no symbols, imports, or importers)", options.onRemoval);
+  const built: CodeGraph = {
+    root: toPosixPath(normalizedRoot),
     generatedAt: new Date().toISOString(),
-    files: parsedFiles,
+    files: keptFiles,
   };
+  const graph = options.packages ? assignPackages(built, normalizedRoot) : built;
+
+  const focused = options.focusPaths?.length ? focusGraph(graph, { paths: options.focusPaths, depth: options.focusDepth }) : graph;
+  const focusedPaths = new Set(focused.files.map((file) => file.path));
+  const focusRule = (file: FileGraph) => (focusedPaths.has(file.path) ? "--focus-path neighbor kept as a stub" : "outside the --focus-path neighborhood");
+  reportRemovals(graph.files, focused.files, "focus", focusRule, options.onRemoval);
+  const summarized = options.externalNoiseThreshold !== undefined ? suppressCommonExternals(focused, options.externalNoiseThreshold) : focused;
+  const reversed = options.reverseImports ? addReverseImports(summarized) : summarized;
+  return options.fileDependencies ? addFileDependencies(reversed) : reversed;
+}
+
+const SOURCE_SPECIFIER = /(^|\/)[^/.]*$|\.[cm]?[jt]sx?$/;
+
+/**
+ * Relative imports that point at no file in the graph, usually a typo or a file excluded from the scan.
+ * Imports of assets such as `./styles.css` are not reported.
+ */
+export function findUnresolvedImports(graph: CodeGraph): { file: string; specifier: string }[] {
+  return graph.files
+    .filter((file) => !file.stub)
+    .flatMap((file) =>
+      file.imports
+        .filter((edge) => edge.from.startsWith(".") && !edge.resolved && SOURCE_SPECIFIER.test(edge.from))
+        .map((edge) => ({ file: file.path, specifier: edge.from }))
+    );
+}
+
+export function findDegradedFiles(graph: CodeGraph): FileGraph[] {
+  return graph.files.filter((file) => (file.parseErrors ?? 0) > 0);
+}
+
+/**
+ * Share of files that parsed without syntax errors, between 0 and 1.
+ */
+export function parseHealth(graph: CodeGraph): number {
+  if (graph.files.length === 0) {
+    return 1;
+  }
+
+  return 1 - findDegradedFiles(graph).length / graph.files.length;
 }
@@ -0,0 +1,60 @@
+import fs from "fs";
+import path from "path";
+import { globToRegExp } from "./glob";
+
+export const IGNORE_FILE = ".ycgignore";
+
+export interface IgnoreRule {
+  pattern: RegExp;
+  /** `!pattern`: re-include paths an earlier rule ignored. */
+  negated: boolean;
+}
+
+/**
+ * Parses ignore rules in `.gitignore` syntax: `#` comments, `!` negation, a leading `/` anchoring the
+ * pattern to the root, and patterns without a slash matching at any depth.
+ */
+export function parseIgnoreRules(content: string): IgnoreRule[] {
+  return content
+    .split(/\r?\n/)
+    .map((line) => line.trim())
+    .filter((line) => line && !line.startsWith("#"))
+    .map((line) => {
+      const negated = line.startsWith("!");
+      const body = (negated ? line.slice(1) : line).replace(/\/$/, "");
+      const anchored = body.startsWith("/") || body.includes("/");
+      const glob = body.replace(/^\//, "");
+      return { pattern: globToRegExp(anchored ? glob : `**/${glob}`), negated };
+    });
+}
+
+function matchesRules(relativePath: string, rules: IgnoreRule[]): boolean {
+  let ignored = false;
+  for (const rule of rules) {
+    if (rule.pattern.test(relativePath)) {
+      ignored = !rule.negated;
+    }
+  }
+
+  return ignored;
+}
+
+/**
+ * Whether a forward-slash path relative to the root is ignored; the last matching rule wins. A path
+ * inside an ignored directory stays ignored, as with git.
+ */
+export function isIgnored(relativePath: string, rules: IgnoreRule[]): boolean {
+  const segments = relativePath.split("/");
+  for (let depth = 1; depth < segments.length; depth += 1) {
+    if (matchesRules(segments.slice(0, depth).join("/"), rules)) {
+      return true;
+    }
+  }
+
+  return matchesRules(relativePath, rules);
+}
+
+export function loadIgnoreRules(root: string): IgnoreRule[] {
+  const ignorePath = path.join(root, IGNORE_FILE);
+  return fs.existsSync(ignorePath) ? parseIgnoreRules(fs.readFileSync(ignorePath, "utf8")) : [];
+}
@@ -0,0 +1,37 @@
+import { FileGraph } from "./types";
+
+interface Implementor {
+  file: FileGraph;
+  className: string;
+}
+
+function importsSymbolFrom(file: FileGraph, target: string, name: string): boolean {
+  return file.imports.some((edge) => edge.resolved === target && edge.symbols.some((symbol) => symbol === name || symbol.endsWith(` as ${name}`)));
+}
+
+/**
+ * Sets `implementedBy` on interfaces that classes name in their `implements` clause. A class counts when
+ * it is declared in the interface's file or imports the interface from it; when no class qualifies that
+ * way, classes anywhere in the graph naming an interface of that name are used, which covers re-exports
+ * through barrel files at the cost of possible false matches between same-named interfaces.
+ */
+export function resolveImplementations(files: FileGraph[]): FileGraph[] {
+  const implementors = new Map<string, Implementor[]>();
+  for (const file of files) {
+    for (const symbol of file.symbols) {
+      for (const name of symbol.implements ?? []) {
+        implementors.set(name, [...(implementors.get(name) ?? []), { file, className: symbol.name }]);
+      }
+    }
+  }
+
+  return files.map((file) => ({
+    ...file,
+    symbols: file.symbols.map((symbol) => {
+      const candidates = symbol.kind === "interface" ? implementors.get(symbol.name) ?? [] : [];
+      const linked = candidates.filter((candidate) => candidate.file === file || importsSymbolFrom(candidate.file, file.path, symbol.name));
+      const chosen = linked.length > 0 ? linked : candidates;
+      return chosen.length > 0 ? { ...symbol, implementedBy: chosen.map((candidate) => `${candidate.file.path}#${candidate.className}`) } : symbol;
+    }),
+  }));
+}
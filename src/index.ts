@@ -1,3 +1,58 @@
-export { buildGraph, GraphOptions } from "./graph";
-export { serializeGraph } from "./yamlSerializer";
+export { BarrelCollapse, collapseBarrels, isBarrelFile } from "./barrels";
+export { BudgetResult, fitToTokenBudget, TokenBudgetError } from "./budget";
+export { hashContent, openParseCache, ParseCache } from "./cache";
+export { chunkGraph, chunkPath, GraphChunk, manifestPath, renderChunkManifest } from "./chunks";
+export { ConfigError, ConfigErrorKind, validateConfig, ValidatedConfig, YcgConfig } from "./config";
+export { addFileDependencies } from "./dependencies";
+export { DeclarationMerge, DeclarationMode, DEFAULT_DECLARATION_REWRITES, isDeclarationFile, mergeDeclarationFiles } from "./declarations";
+export { DEFAULT_RENAME_THRESHOLD, DiffFormat, DiffOptions, diffGraphs, formatGraphDiff, GraphDiff, ImportChange, renderGraphDiff, SimilaritySignal, SymbolChange, SymbolChangeKind } from "./diff";
+export { applyDocPolicy, DocPolicy } from "./docs";
+export { filterImports, IMPORT_EDGE_KINDS, ImportEdgeKind, ImportFilterOptions, pruneOrphans } from "./edges";
+export { Diagnostic, DiagnosticCode, DiagnosticCodes, DiagnosticLevel, formatDiagnostic } from "./diagnostics";
+export { estimateGraph, estimateTokens, GraphEstimate } from "./estimate";
+export { suppressCommonExternals } from "./externals";
+export { renderDot, renderGraphMl } from "./exporters";
+export { focusGraph, FocusOptions } from "./focus";
+export { globToRegExp, matchesAnyGlob } from "./glob";
+export { buildGraph, findUnresolvedImports, GraphOptions, Removal, RemovalStage } from "./graph";
+export { addFile, addImport, AddImportOptions, addSymbol, GraphMutationError, mergeGraphs, MergeStrategy } from "./mutations";
+export { checkOutputTarget, OutputFormat, OutputTarget, parseEmitSpec, renderOutput, streamOutput, writeFileAtomic } from "./outputs";
+export { resolveImplementations } from "./implementations";
+export { assignPackages } from "./packages";
+export { matchSymbols, QueryDirection, QueryOptions, querySubgraph, SymbolMatch } from "./query";
+export { relativePosixPath, toPosixPath } from "./paths";
+export { IGNORE_FILE, IgnoreRule, isIgnored, loadIgnoreRules, parseIgnoreRules } from "./ignore";
+export { addReverseImports } from "./reverse";
+export { embedRunMetadata } from "./run";
+export { GRAPH_SCHEMA_VERSION, isNewerSchema, migrateGraph, UNVERSIONED_SCHEMA } from "./schema";
+export { createImportResolver, ImportResolver, loadPathAliases, resolveImports } from "./resolver";
+export {
+  DEFAULT_STYLES,
+  EdgeStyleKind,
+  LineStyle,
+  NodeShape,
+  NodeStyleKind,
+  ResolvedStyle,
+  ResolvedStyles,
+  resolveStyles,
+  Style,
+  StyleConfig,
+} from "./styles";
+export { ClassSummaryMode } from "./summaries";
+export { computeGraphStats, computeTokenReport, GraphStats, TokenReport, TokenShare } from "./stats";
+export { isTestFile, tagTestFiles, TEST_FILE_GLOBS, TestFileMode } from "./testFiles";
+export { GraphProblem, ProblemLevel, validateGraph } from "./validate";
+export { createWatchLoop, Scheduler, SourceWatchOptions, watchSourceTree, WatchLoop, WatchLoopOptions } from "./watch";
+export {
+  deserializeGraph,
+  GRAPH_SECTIONS,
+  GraphDocument,
+  GraphSection,
+  readGraphDocument,
+  selectSections,
+  SerializationError,
+  serializeGraph,
+  SerializeOptions,
+  writeGraph,
+} from "./yamlSerializer";
 export * from "./types";
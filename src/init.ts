@@ -0,0 +1,144 @@
+import fs from "fs";
+import path from "path";
+import { ConfigFile, DEFAULT_CONFIG_FILE, validateConfig } from "./config";
+import { scanSourceFiles } from "./scanner";
+
+export type CiProvider = "github" | "gitlab";
+
+export interface ProjectProfile {
+  languages: string[];
+  frameworks: string[];
+  fileCount: number;
+}
+
+const LANGUAGE_EXTENSIONS: Record<string, string> = {
+  ".ts": "typescript",
+  ".tsx": "typescript",
+  ".mts": "typescript",
+  ".cts": "typescript",
+  ".js": "javascript",
+  ".jsx": "javascript",
+  ".mjs": "javascript",
+  ".cjs": "javascript",
+  ".rs": "rust",
+  ".py": "python",
+  ".go": "go",
+};
+
+const FRAMEWORK_PACKAGES: Record<string, string> = {
+  "@nestjs/core": "nestjs",
+  "@angular/core": "angular",
+  react: "react",
+  next: "next",
+  vue: "vue",
+  express: "express",
+};
+
+const LARGE_PROJECT_FILES = 2000;
+
+function readDependencies(root: string): string[] {
+  const manifestPath = path.join(root, "package.json");
+  if (!fs.existsSync(manifestPath)) {
+    return [];
+  }
+
+  try {
+    const manifest = JSON.parse(fs.readFileSync(manifestPath, "utf8"));
+    return [...Object.keys(manifest.dependencies ?? {}), ...Object.keys(manifest.devDependencies ?? {})];
+  } catch {
+    return [];
+  }
+}
+
+export function detectProject(root: string): ProjectProfile {
+  const scan = scanSourceFiles(root, Object.keys(LANGUAGE_EXTENSIONS));
+  const languageCounts = new Map<string, number>();
+
+  for (const file of scan.files) {
+    const language = LANGUAGE_EXTENSIONS[path.extname(file)];
+    languageCounts.set(language, (languageCounts.get(language) ?? 0) + 1);
+  }
+
+  const dependencies = new Set(readDependencies(root));
+  const frameworks = Object.entries(FRAMEWORK_PACKAGES)
+    .filter(([packageName]) => dependencies.has(packageName))
+    .map(([, framework]) => framework);
+
+  return {
+    languages: Array.from(languageCounts.entries())
+      .sort((a, b) => b[1] - a[1])
+      .map(([language]) => language),
+    frameworks,
+    fileCount: scan.files.length,
+  };
+}
+
+/**
+ * Proposes a configuration for a detected project. The result always passes `validateConfig`.
+ */
+export function proposeConfig(profile: ProjectProfile): ConfigFile {
+  const extensions = [
+    ...(profile.languages.includes("typescript") ? [".ts", ".tsx"] : []),
+    ...(profile.languages.includes("javascript") ? [".js", ".jsx", ".mjs", ".cjs"] : []),
+  ];
+  const usesDecorators = profile.frameworks.includes("nestjs") || profile.frameworks.includes("angular");
+
+  const config: ConfigFile = {
+    out: "graph.yaml",
+    ...(extensions.length > 0 ? { extensions } : {}),
+    classSummaries: usesDecorators ? "heuristic" : "doc-only",
+    ...(profile.fileCount > LARGE_PROJECT_FILES ? { shortKeys: true } : {}),
+  };
+
+  validateConfig(config);
+  return config;
+}
+
+export function renderConfigFile(config: ConfigFile): string {
+  return `${JSON.stringify(config, null, 2)}\n`;
+}
+
+export function ciSnippetPath(provider: CiProvider): string {
+  return provider === "github" ? path.join(".github", "workflows", "ycg.yml") : "ycg.gitlab-ci.yml";
+}
+
+export function renderCiSnippet(provider: CiProvider, configPath = DEFAULT_CONFIG_FILE): string {
+  if (provider === "github") {
+    return `name: Code graph
+
+on:
+  push:
+    branches: [ "main", "master" ]
+  pull_request:
+
+jobs:
+  graph:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: npm
+      - run: npm ci
+      - run: npx ycg --config ${configPath}
+      - uses: actions/upload-artifact@v4
+        with:
+          name: code-graph
+          path: graph.yaml
+`;
+  }
+
+  return `code-graph:
+  image: node:20
+  cache:
+    key:
+      files: [package-lock.json]
+    paths: [.npm/]
+  script:
+    - npm ci --cache .npm --prefer-offline
+    - npx ycg --config ${configPath}
+  artifacts:
+    paths: [graph.yaml]
+`;
+}
@@ -0,0 +1,94 @@
+import ts from "typescript";
+
+const printer = ts.createPrinter({ removeComments: true });
+
+function print(node: ts.Node, sourceFile: ts.SourceFile): string {
+  return printer.printNode(ts.EmitHint.Unspecified, node, sourceFile);
+}
+
+function isHidden(member: ts.ClassElement): boolean {
+  if (member.name && ts.isPrivateIdentifier(member.name)) {
+    return true;
+  }
+
+  const modifiers = ts.canHaveModifiers(member) ? ts.getModifiers(member) : undefined;
+  return Boolean(modifiers?.some((modifier) => modifier.kind === ts.SyntaxKind.PrivateKeyword || modifier.kind === ts.SyntaxKind.ProtectedKeyword));
+}
+
+function formatMethod(node: ts.MethodDeclaration | ts.MethodSignature, sourceFile: ts.SourceFile): string {
+  const optional = node.questionToken ? "?" : "";
+  const parameters = node.parameters.map((param) => print(param, sourceFile)).join(", ");
+  const returnType = node.type ? `: ${print(node.type, sourceFile)}` : "";
+  return `${node.name.getText(sourceFile)}${optional}(${parameters})${returnType}`;
+}
+
+function formatProperty(node: ts.PropertyDeclaration | ts.PropertySignature, sourceFile: ts.SourceFile): string {
+  const optional = node.questionToken ? "?" : "";
+  const type = node.type ? `: ${print(node.type, sourceFile)}` : "";
+  return `${node.name.getText(sourceFile)}${optional}${type}`;
+}
+
+/** `get name(): T` and `set name(value: T)`, so accessors stay distinct from methods such as `getName()`. */
+function formatAccessor(node: ts.AccessorDeclaration, sourceFile: ts.SourceFile): string {
+  const prefix = ts.isGetAccessorDeclaration(node) ? "get" : "set";
+  const parameters = node.parameters.map((param) => print(param, sourceFile)).join(", ");
+  const returnType = node.type ? `: ${print(node.type, sourceFile)}` : "";
+  return `${prefix} ${node.name.getText(sourceFile)}(${parameters})${returnType}`;
+}
+
+/** `[key: string]: T`; index signatures have no name of their own. */
+function formatIndexSignature(node: ts.IndexSignatureDeclaration, sourceFile: ts.SourceFile): string {
+  const parameters = node.parameters.map((param) => print(param, sourceFile)).join(", ");
+  return `[${parameters}]: ${print(node.type, sourceFile)}`;
+}
+
+/** The implementation of an overloaded method, whose signature callers never see. */
+function isOverloadImplementation(member: ts.MethodDeclaration, node: ts.ClassDeclaration, sourceFile: ts.SourceFile): boolean {
+  const name = member.name.getText(sourceFile);
+  return Boolean(member.body) && node.members.some((other) => ts.isMethodDeclaration(other) && !other.body && other.name.getText(sourceFile) === name);
+}
+
+/**
+ * Compact signatures of the public methods, properties, and accessors of a class, such as
+ * `findOne(id: string): Promise<User>`, `name?: string`, and `get total(): number`. Computed names keep
+ * their brackets (`[Symbol.iterator](): Iterator<T>`). Constructors, static blocks, and private or
+ * protected members are left out; overloaded methods are listed once per overload.
+ */
+export function classMembers(node: ts.ClassDeclaration, sourceFile: ts.SourceFile): string[] {
+  return node.members
+    .filter((member) => !isHidden(member))
+    .flatMap((member) => {
+      if (ts.isMethodDeclaration(member)) {
+        return isOverloadImplementation(member, node, sourceFile) ? [] : [formatMethod(member, sourceFile)];
+      }
+      if (ts.isPropertyDeclaration(member)) {
+        return [formatProperty(member, sourceFile)];
+      }
+      if (ts.isGetAccessorDeclaration(member) || ts.isSetAccessorDeclaration(member)) {
+        return [formatAccessor(member, sourceFile)];
+      }
+      if (ts.isIndexSignatureDeclaration(member)) {
+        return [formatIndexSignature(member, sourceFile)];
+      }
+      return [];
+    });
+}
+
+/** Compact signatures of the methods, properties, accessors, and index signatures an interface declares, in declaration order. */
+export function interfaceMembers(node: ts.InterfaceDeclaration, sourceFile: ts.SourceFile): string[] {
+  return node.members.flatMap((member) => {
+    if (ts.isMethodSignature(member)) {
+      return [formatMethod(member, sourceFile)];
+    }
+    if (ts.isPropertySignature(member)) {
+      return [formatProperty(member, sourceFile)];
+    }
+    if (ts.isGetAccessorDeclaration(member) || ts.isSetAccessorDeclaration(member)) {
+      return [formatAccessor(member, sourceFile)];
+    }
+    if (ts.isIndexSignatureDeclaration(member)) {
+      return [formatIndexSignature(member, sourceFile)];
+    }
+    return [];
+  });
+}
@@ -0,0 +1,158 @@
+import { CodeGraph, CommonExternal, FileGraph, ImportEdge, SymbolNode, WorkspacePackage } from "./types";
+
+export type MergeStrategy = "skip" | "overwrite" | "error";
+
+export class GraphMutationError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "GraphMutationError";
+  }
+}
+
+// Invariants kept by every helper in this module, so serializers can emit a graph as-is:
+// - `files` is sorted by `path` in code-unit order, as the scanner sorts them, and paths are unique;
+// - within a file, a symbol is unique by `name` + `kind` and symbols are ordered by location;
+// - every `resolved` import target added here is a file of the graph.
+// Import edges are not unique per module: `buildGraph` keeps one edge per import statement, and
+// `addImport` merges into the first edge with the same module specifier and kind.
+
+export interface AddImportOptions {
+  /** Add a `stub: true` file for a `resolved` path missing from the graph, instead of throwing. */
+  stub?: boolean;
+}
+
+/** Code-unit order, the order of `Array.prototype.sort`; `localeCompare` would put `src/a.ts` before `src/B.ts`. */
+function comparePaths(a: FileGraph, b: FileGraph): number {
+  return a.path < b.path ? -1 : a.path > b.path ? 1 : 0;
+}
+
+function compareLocation(a: SymbolNode, b: SymbolNode): number {
+  return a.location.line - b.location.line || a.location.column - b.location.column;
+}
+
+function findFile(graph: CodeGraph, filePath: string): FileGraph {
+  const file = graph.files.find((candidate) => candidate.path === filePath);
+  if (!file) {
+    throw new GraphMutationError(`Unknown file "${filePath}"`);
+  }
+
+  return file;
+}
+
+function insertSorted<T>(items: T[], item: T, compare: (a: T, b: T) => number): void {
+  const index = items.findIndex((existing) => compare(item, existing) < 0);
+  if (index === -1) {
+    items.push(item);
+  } else {
+    items.splice(index, 0, item);
+  }
+}
+
+export function addFile(graph: CodeGraph, file: FileGraph): void {
+  if (graph.files.some((existing) => existing.path === file.path)) {
+    throw new GraphMutationError(`File "${file.path}" already exists in the graph`);
+  }
+
+  insertSorted(graph.files, file, comparePaths);
+}
+
+export function addSymbol(graph: CodeGraph, filePath: string, symbol: SymbolNode): void {
+  const file = findFile(graph, filePath);
+  if (file.symbols.some((existing) => existing.name === symbol.name && existing.kind === symbol.kind)) {
+    throw new GraphMutationError(`Symbol "${symbol.name}" (${symbol.kind}) already exists in "${filePath}"`);
+  }
+
+  insertSorted(file.symbols, symbol, compareLocation);
+}
+
+export function addImport(graph: CodeGraph, filePath: string, edge: ImportEdge, options: AddImportOptions = {}): void {
+  const file = findFile(graph, filePath);
+  const missing = edge.resolved && !graph.files.some((candidate) => candidate.path === edge.resolved) ? edge.resolved : undefined;
+  if (missing && !options.stub) {
+    throw new GraphMutationError(`Import "${edge.from}" in "${filePath}" resolves to "${missing}", which is not in the graph`);
+  }
+
+  const existing = file.imports.find((candidate) => candidate.from === edge.from && candidate.kind === edge.kind);
+
+  if (existing?.resolved && edge.resolved && existing.resolved !== edge.resolved) {
+    throw new GraphMutationError(`Import "${edge.from}" in "${filePath}" resolves to both "${existing.resolved}" and "${edge.resolved}"`);
+  }
+  if (missing) {
+    addFile(graph, { path: missing, imports: [], symbols: [], stub: true });
+  }
+
+  if (!existing) {
+    file.imports.push({ ...edge, symbols: Array.from(new Set(edge.symbols)) });
+    return;
+  }
+
+  if (edge.resolved) {
+    existing.resolved = edge.resolved;
+  }
+
+  for (const symbol of edge.symbols) {
+    if (!existing.symbols.includes(symbol)) {
+      existing.symbols.push(symbol);
+    }
+  }
+}
+
+/** Adds up the importer counts of modules listed in both graphs, most imported first. */
+function mergeCommonExternals(target: CommonExternal[], source: CommonExternal[]): CommonExternal[] {
+  const references = new Map<string, number>();
+  for (const external of [...target, ...source]) {
+    references.set(external.module, (references.get(external.module) ?? 0) + external.references);
+  }
+
+  return Array.from(references, ([module, count]) => ({ module, references: count })).sort(
+    (a, b) => b.references - a.references || a.module.localeCompare(b.module)
+  );
+}
+
+/** Packages of both graphs by path; the target's entry wins for a path found in both. */
+function mergePackages(target: WorkspacePackage[], source: WorkspacePackage[]): WorkspacePackage[] {
+  const packages = new Map(source.map((entry) => [entry.path, entry]));
+  target.forEach((entry) => packages.set(entry.path, entry));
+  return Array.from(packages.values()).sort((a, b) => a.path.localeCompare(b.path));
+}
+
+/**
+ * Merges every file of `source` into `target`, along with its `commonExternals` (importer counts are
+ * added up) and `packages`. Files present in both graphs are resolved by `strategy`: `skip` keeps the
+ * target entry, `overwrite` replaces it and `error` throws a GraphMutationError before `target` is changed.
+ */
+export function mergeGraphs(target: CodeGraph, source: CodeGraph, strategy: MergeStrategy = "error"): void {
+  if (strategy === "error") {
+    const conflict = source.files.find((file) => target.files.some((existing) => existing.path === file.path));
+    if (conflict) {
+      throw new GraphMutationError(`File "${conflict.path}" exists in both graphs`);
+    }
+  }
+
+  for (const file of source.files) {
+    const index = target.files.findIndex((existing) => existing.path === file.path);
+
+    if (index === -1) {
+      addFile(target, file);
+      continue;
+    }
+
+    switch (strategy) {
+      case "skip":
+        break;
+      case "overwrite":
+        target.files[index] = file;
+        break;
+      case "error":
+      default:
+        throw new GraphMutationError(`File "${file.path}" exists in both graphs`);
+    }
+  }
+
+  if (source.commonExternals) {
+    target.commonExternals = mergeCommonExternals(target.commonExternals ?? [], source.commonExternals);
+  }
+  if (source.packages) {
+    target.packages = mergePackages(target.packages ?? [], source.packages);
+  }
+}
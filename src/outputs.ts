@@ -0,0 +1,216 @@
+import fs from "fs";
+import path from "path";
+import { ConfigError } from "./config";
+import { addFileDependencies } from "./dependencies";
+import { filterImports, IMPORT_EDGE_KINDS, ImportEdgeKind } from "./edges";
+import { renderDot, renderGraphMl } from "./exporters";
+import { focusGraph } from "./focus";
+import { addReverseImports } from "./reverse";
+import { CodeGraph, FileGraph, SymbolKind } from "./types";
+import { SYMBOL_KINDS } from "./validate";
+import { generateGraphHtml, VisualizationOptions } from "./visualizer";
+import { selectSections, SerializeOptions, serializeGraph, writeGraph } from "./yamlSerializer";
+
+export type OutputFormat = "yaml" | "html" | "dot" | "graphml";
+
+export const OUTPUT_FORMATS: readonly OutputFormat[] = ["yaml", "html", "dot", "graphml"];
+
+/** Filters applied to one output only, on a copy of the shared graph. */
+export interface OutputFilters {
+  publicOnly?: boolean;
+  includeKinds?: SymbolKind[];
+  excludeKinds?: SymbolKind[];
+  excludeImports?: ImportEdgeKind[];
+  focusPaths?: string[];
+  focusDepth?: number;
+}
+
+export interface OutputTarget {
+  path: string;
+  format: OutputFormat;
+  options: SerializeOptions;
+  filters?: OutputFilters;
+}
+
+const EMIT_OPTIONS: Record<string, Partial<OutputTarget>> = {
+  "short-keys": { options: { shortKeys: true } },
+  "no-metadata": { options: { metadata: false } },
+  html: { format: "html" },
+  dot: { format: "dot" },
+  graphml: { format: "graphml" },
+};
+
+/** `--emit` options that take a value, `name=value`; lists are joined with `+` since `,` separates options. */
+const EMIT_FILTERS: Record<string, (value: string, spec: string) => OutputFilters> = {
+  "public-only": () => ({ publicOnly: true }),
+  "include-kinds": (value, spec) => ({ includeKinds: parseEmitList(value, SYMBOL_KINDS, "include-kinds", spec) }),
+  "exclude-kinds": (value, spec) => ({ excludeKinds: parseEmitList(value, SYMBOL_KINDS, "exclude-kinds", spec) }),
+  "exclude-imports": (value, spec) => ({ excludeImports: parseEmitList(value, IMPORT_EDGE_KINDS, "exclude-imports", spec) }),
+  focus: (value) => ({ focusPaths: [value] }),
+  depth: (value, spec) => {
+    const depth = Number(value);
+    if (!Number.isInteger(depth) || depth < 0) {
+      throw new ConfigError("invalid-value", `depth must be a non-negative integer in "${spec}", got ${value}`, "Try depth=1");
+    }
+    return { focusDepth: depth };
+  },
+};
+
+function parseEmitList<T extends string>(value: string, choices: readonly T[], name: string, spec: string): T[] {
+  const values = value.split("+");
+  const unknown = values.filter((entry) => !choices.includes(entry as T));
+  if (unknown.length > 0) {
+    throw new ConfigError("invalid-value", `Unknown ${name} value(s) ${unknown.join(", ")} in "${spec}"`, `Supported values: ${choices.join("+")}`);
+  }
+  return values as T[];
+}
+
+function emitOptionName(option: string): string {
+  return option.split("=")[0];
+}
+
+function isEmitOption(option: string): boolean {
+  return option in EMIT_OPTIONS || emitOptionName(option) in EMIT_FILTERS;
+}
+
+/**
+ * Rejects serialization options that have no effect on the target's format.
+ */
+export function checkOutputTarget(target: OutputTarget): OutputTarget {
+  if (target.format !== "yaml" && (target.options.shortKeys || target.options.metadata === false)) {
+    throw new ConfigError(
+      "conflicting-flags",
+      `--short-keys and --no-metadata only apply to YAML output, but ${target.path} is written as ${target.format}`,
+      "Drop those flags or use --format yaml"
+    );
+  }
+
+  return target;
+}
+
+/**
+ * Parses an `--emit` specification, `<file>[:option,option...]`, into an output target whose options
+ * override `base`. Options are serialization settings, formats, or per-output filters (`public-only`,
+ * `include-kinds=class+function`, `exclude-kinds=...`, `exclude-imports=...`, `focus=<glob>`, `depth=<n>`).
+ * The part after the last colon is only treated as options when every entry is a known option, so
+ * Windows drive letters are left alone.
+ */
+export function parseEmitSpec(spec: string, base: SerializeOptions = {}, format: OutputFormat = "yaml"): OutputTarget {
+  const separator = spec.lastIndexOf(":");
+  const optionList = separator > 0 ? spec.slice(separator + 1).split(",").filter(Boolean) : [];
+
+  if (optionList.length === 0 || !optionList.every(isEmitOption)) {
+    if (optionList.length > 0 && separator > 1) {
+      const unknown = optionList.filter((option) => !isEmitOption(option));
+      throw new ConfigError(
+        "invalid-value",
+        `Unknown --emit option(s) ${unknown.join(", ")} in "${spec}"`,
+        `Supported options: ${[...Object.keys(EMIT_OPTIONS), ...Object.keys(EMIT_FILTERS)].join(", ")}`
+      );
+    }
+    return checkOutputTarget({ path: spec, format, options: { ...base } });
+  }
+
+  return checkOutputTarget(
+    optionList.reduce<OutputTarget>(
+      (target, option) => {
+        if (!(option in EMIT_OPTIONS)) {
+          const [name, ...value] = option.split("=");
+          const filters = EMIT_FILTERS[name](value.join("="), spec);
+          const focusPaths = [...(target.filters?.focusPaths ?? []), ...(filters.focusPaths ?? [])];
+          return { ...target, filters: { ...target.filters, ...filters, ...(focusPaths.length > 0 ? { focusPaths } : {}) } };
+        }
+        return { ...target, ...EMIT_OPTIONS[option], options: { ...target.options, ...EMIT_OPTIONS[option].options } };
+      },
+      { path: spec.slice(0, separator), format, options: { ...base } }
+    )
+  );
+}
+
+function keepSymbols(file: FileGraph, filters: OutputFilters): FileGraph {
+  const { publicOnly, includeKinds, excludeKinds } = filters;
+  const symbols = file.symbols.filter(
+    (symbol) =>
+      (!publicOnly || symbol.exported) && (!includeKinds?.length || includeKinds.includes(symbol.kind)) && !excludeKinds?.includes(symbol.kind)
+  );
+  return { ...file, symbols };
+}
+
+/**
+ * Applies an output's own filters to a copy of the shared graph, in the order the build applies them:
+ * imports, then symbols, then focus. `importedBy` and `dependsOn` lists are rebuilt from the imports that
+ * remain, so they never name removed files or edges.
+ */
+export function applyOutputFilters(graph: CodeGraph, filters: OutputFilters = {}): CodeGraph {
+  if (Object.keys(filters).length === 0) {
+    return graph;
+  }
+
+  const trimmed = filterImports(graph.files, { excludeKinds: filters.excludeImports }).map((file) => keepSymbols(file, filters));
+  const filtered = { ...graph, files: trimmed };
+  const focused = filters.focusPaths?.length ? focusGraph(filtered, { paths: filters.focusPaths, depth: filters.focusDepth }) : filtered;
+
+  const reversed = graph.files.some((file) => file.importedBy);
+  const dependencies = graph.files.some((file) => file.dependsOn);
+  const stripped = {
+    ...focused,
+    files: focused.files.map((file) => {
+      const { importedBy: _importedBy, dependsOn: _dependsOn, ...rest } = file;
+      return rest;
+    }),
+  };
+  const withImporters = reversed ? addReverseImports(stripped) : stripped;
+  return dependencies ? addFileDependencies(withImporters) : withImporters;
+}
+
+export function renderOutput(graph: CodeGraph, target: OutputTarget, visualization: VisualizationOptions = {}): string {
+  switch (target.format) {
+    case "html":
+      return generateGraphHtml(selectSections(graph, target.options.sections), visualization);
+    case "dot":
+      return renderDot(selectSections(graph, target.options.sections), visualization);
+    case "graphml":
+      return renderGraphMl(selectSections(graph, target.options.sections), visualization);
+    default:
+      return serializeGraph(graph, target.options);
+  }
+}
+
+/** Like `renderOutput`, but passes YAML to `write` in chunks instead of returning one string. */
+export function streamOutput(
+  graph: CodeGraph,
+  target: OutputTarget,
+  write: (chunk: string) => void,
+  visualization: VisualizationOptions = {}
+): void {
+  if (target.format === "yaml") {
+    writeGraph(graph, target.options, write);
+  } else {
+    write(renderOutput(graph, target, visualization));
+  }
+}
+
+/**
+ * Writes `content` to a temporary file next to `filePath` and renames it into place, so an interrupted
+ * run never leaves a truncated output behind. `content` may be a function that writes chunks.
+ */
+export function writeFileAtomic(filePath: string, content: string | ((write: (chunk: string) => void) => void)): void {
+  const temporaryPath = path.join(path.dirname(filePath), `.${path.basename(filePath)}.${process.pid}.tmp`);
+
+  try {
+    const descriptor = fs.openSync(temporaryPath, "w");
+    try {
+      if (typeof content === "string") {
+        fs.writeSync(descriptor, content);
+      } else {
+        content((chunk) => fs.writeSync(descriptor, chunk));
+      }
+    } finally {
+      fs.closeSync(descriptor);
+    }
+    fs.renameSync(temporaryPath, filePath);
+  } catch (error) {
+    fs.rmSync(temporaryPath, { force: true });
+    throw error;
+  }
+}
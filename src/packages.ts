@@ -0,0 +1,56 @@
+import fs from "fs";
+import path from "path";
+import { CodeGraph, WorkspacePackage } from "./types";
+
+function readPackageName(directory: string): string | undefined {
+  const manifestPath = path.join(directory, "package.json");
+  if (!fs.existsSync(manifestPath)) {
+    return undefined;
+  }
+
+  try {
+    const name = JSON.parse(fs.readFileSync(manifestPath, "utf8")).name;
+    return typeof name === "string" && name ? name : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * Sets `package` on every file to the name in the nearest `package.json` at or below the graph root,
+ * and lists the packages found under `packages`. Used for monorepos where the same symbol name exists
+ * in several packages.
+ */
+export function assignPackages(graph: CodeGraph, root: string): CodeGraph {
+  const normalizedRoot = path.resolve(root);
+  const byDirectory = new Map<string, WorkspacePackage | undefined>();
+
+  function packageOf(relativeDirectory: string): WorkspacePackage | undefined {
+    if (byDirectory.has(relativeDirectory)) {
+      return byDirectory.get(relativeDirectory);
+    }
+
+    const name = readPackageName(path.join(normalizedRoot, relativeDirectory));
+    const parent = relativeDirectory === "." ? undefined : path.posix.dirname(relativeDirectory);
+    const found = name ? { name, path: relativeDirectory } : parent !== undefined ? packageOf(parent) : undefined;
+    byDirectory.set(relativeDirectory, found);
+    return found;
+  }
+
+  const packages = new Map<string, WorkspacePackage>();
+  const files = graph.files.map((file) => {
+    const found = packageOf(path.posix.dirname(file.path));
+    if (!found) {
+      return file;
+    }
+
+    packages.set(found.path, found);
+    return { ...file, package: found.name };
+  });
+
+  return {
+    ...graph,
+    files,
+    ...(packages.size > 0 ? { packages: Array.from(packages.values()).sort((a, b) => a.path.localeCompare(b.path)) } : {}),
+  };
+}
@@ -1,10 +1,33 @@
 import fs from "fs";
-import path from "path";
 import ts from "typescript";
-import { FileGraph, ImportEdge, SymbolNode } from "./types";
+import { functionLikeOf, measureComplexity } from "./complexity";
+import { applyDocPolicy, DocPolicy } from "./docs";
+import { classMembers, interfaceMembers } from "./members";
+import { relativePosixPath } from "./paths";
+import { detectRole } from "./roles";
+import { ClassSummaryMode, summarizeClass } from "./summaries";
+import { FileGraph, ImportEdge, ImportKind, Location, SymbolNode } from "./types";
 
 const printer = ts.createPrinter({ removeComments: true });
 
+export interface ParserOptions {
+  classSummaries?: ClassSummaryMode;
+  /** Record `complexity` and `loc` on functions and function-valued variables. */
+  complexity?: boolean;
+  /** Record where each declaration ends, so tools can map symbols back to a source range. */
+  ranges?: boolean;
+  /** How JSDoc text is shortened before it is stored in `doc`. */
+  docPolicy?: DocPolicy;
+  /** Set to false to skip reading doc comments; symbols then carry no `doc`. */
+  docs?: boolean;
+  /** Set to false to skip rendering declarations; symbols then carry no `signature`. */
+  signatures?: boolean;
+  /** Record the interfaces each class names in its `implements` clause. */
+  implementations?: boolean;
+  /** List the public methods and properties of classes and interfaces in `members`. */
+  members?: boolean;
+}
+
 function hasExportModifier(modifiers?: readonly ts.ModifierLike[]): boolean {
   return Boolean(modifiers?.some((modifier) => modifier.kind === ts.SyntaxKind.ExportKeyword));
 }
@@ -14,15 +37,36 @@ function getLocation(sourceFile: ts.SourceFile, node: ts.Node) {
   return { line: line + 1, column: character + 1 };
 }
 
+function getEndLocation(sourceFile: ts.SourceFile, node: ts.Node) {
+  const { line, character } = sourceFile.getLineAndCharacterOfPosition(node.getEnd());
+  return { line: line + 1, column: character + 1 };
+}
+
+function isDefaultExport(node: ts.FunctionDeclaration | ts.ClassDeclaration): boolean {
+  return Boolean(node.modifiers?.some((modifier) => modifier.kind === ts.SyntaxKind.DefaultKeyword));
+}
+
+/**
+ * Name of a function or class declaration. Only default exports can omit it, as in
+ * `export default function () {}`, and importers reach those as `default`.
+ */
+function declarationName(node: ts.FunctionDeclaration | ts.ClassDeclaration, sourceFile: ts.SourceFile): string | undefined {
+  if (node.name) {
+    return node.name.getText(sourceFile);
+  }
+
+  return isDefaultExport(node) ? "default" : undefined;
+}
+
 function formatFunctionSignature(node: ts.FunctionDeclaration, sourceFile: ts.SourceFile): string {
-  const name = node.name?.getText(sourceFile) ?? "anonymous";
+  const name = declarationName(node, sourceFile) ?? "default";
   const parameters = node.parameters.map((param) => printer.printNode(ts.EmitHint.Unspecified, param, sourceFile)).join(", ");
   const returnType = node.type ? printer.printNode(ts.EmitHint.Unspecified, node.type, sourceFile) : "void";
   return `${name}(${parameters}): ${returnType}`;
 }
 
 function formatClassSignature(node: ts.ClassDeclaration, sourceFile: ts.SourceFile): string {
-  const name = node.name?.getText(sourceFile) ?? "anonymous";
+  const name = declarationName(node, sourceFile) ?? "default";
   const heritageClauses = node.heritageClauses?.map((clause) => clause.getText(sourceFile)).join(" ") ?? "";
   return heritageClauses ? `${name} ${heritageClauses}` : name;
 }
@@ -45,6 +89,38 @@ function formatEnumSignature(node: ts.EnumDeclaration, sourceFile: ts.SourceFile
   return members ? `${name} { ${members} }` : name;
 }
 
+/** `namespace A.B {}` is nested as one declaration per segment; returns the innermost one. */
+function innermostNamespace(node: ts.ModuleDeclaration): ts.ModuleDeclaration {
+  return node.body && ts.isModuleDeclaration(node.body) ? innermostNamespace(node.body) : node;
+}
+
+function namespaceName(node: ts.ModuleDeclaration, sourceFile: ts.SourceFile): string {
+  const name = node.name.getText(sourceFile);
+  return node.body && ts.isModuleDeclaration(node.body) ? `${name}.${namespaceName(node.body, sourceFile)}` : name;
+}
+
+function formatNamespaceSignature(node: ts.ModuleDeclaration, sourceFile: ts.SourceFile): string {
+  const name = namespaceName(node, sourceFile);
+  const body = innermostNamespace(node).body;
+  const members = body && ts.isModuleBlock(body)
+    ? body.statements
+        .filter((statement) => hasExportModifier(ts.canHaveModifiers(statement) ? ts.getModifiers(statement) : undefined))
+        .flatMap((statement) => declaredNames(statement, sourceFile))
+    : [];
+  return members.length > 0 ? `${name} { ${members.join(", ")} }` : name;
+}
+
+function declaredNames(statement: ts.Statement, sourceFile: ts.SourceFile): string[] {
+  if (ts.isVariableStatement(statement)) {
+    return statement.declarationList.declarations.map((declaration) => declaration.name.getText(sourceFile));
+  }
+  if (ts.isModuleDeclaration(statement)) {
+    return [namespaceName(statement, sourceFile)];
+  }
+  const name = (statement as ts.Statement & { name?: ts.Node }).name;
+  return name ? [name.getText(sourceFile)] : [];
+}
+
 function stripCommentDelimiters(raw: string): string {
   return raw
     .replace(/^\s*\/\*\*?/, "")
@@ -66,14 +142,22 @@ function extractDoc(node: ts.Node, sourceFile: ts.SourceFile): string | undefine
   return docRange ? stripCommentDelimiters(docRange) : undefined;
 }
 
-function createSymbolNode(kind: SymbolNode["kind"], name: string, node: ts.Node, sourceFile: ts.SourceFile, exported: boolean): SymbolNode {
+function createSymbolNode(
+  kind: SymbolNode["kind"],
+  name: string,
+  node: ts.Node,
+  sourceFile: ts.SourceFile,
+  exported: boolean,
+  options: ParserOptions
+): SymbolNode {
   return {
     kind,
     name,
     exported,
-    signature: deriveSignature(kind, node as never, sourceFile),
-    doc: extractDoc(node, sourceFile),
+    signature: options.signatures === false ? undefined : deriveSignature(kind, node as never, sourceFile),
+    doc: options.docs === false ? undefined : applyDocPolicy(extractDoc(node, sourceFile), options.docPolicy),
     location: getLocation(sourceFile, node),
+    ...(options.ranges ? { end: getEndLocation(sourceFile, node) } : {}),
   };
 }
 
@@ -89,6 +173,8 @@ function deriveSignature(kind: SymbolNode["kind"], node: ts.Node, sourceFile: ts
       return formatTypeAliasSignature(node as ts.TypeAliasDeclaration, sourceFile);
     case "enum":
       return formatEnumSignature(node as ts.EnumDeclaration, sourceFile);
+    case "namespace":
+      return formatNamespaceSignature(node as ts.ModuleDeclaration, sourceFile);
     case "variable":
     default:
       return (node as ts.Node).getText(sourceFile).split(/\r?\n/)[0];
@@ -100,8 +186,9 @@ function parseImport(node: ts.ImportDeclaration, sourceFile: ts.SourceFile): Imp
   const clause = node.importClause;
   const symbols: string[] = [];
 
+  // Written like `import { default as Foo }`, so the name taken from the module stays `default`.
   if (clause?.name) {
-    symbols.push(clause.name.getText(sourceFile));
+    symbols.push(`default as ${clause.name.getText(sourceFile)}`);
   }
 
   if (clause?.namedBindings) {
@@ -112,61 +199,233 @@ function parseImport(node: ts.ImportDeclaration, sourceFile: ts.SourceFile): Imp
     }
   }
 
-  return { from: moduleName, symbols };
+  const kind = importKind(clause);
+  return kind ? { from: moduleName, symbols, kind } : { from: moduleName, symbols };
+}
+
+function importKind(clause: ts.ImportClause | undefined): ImportKind | undefined {
+  if (!clause) {
+    return "side-effect";
+  }
+
+  if (clause.isTypeOnly) {
+    return "type";
+  }
+
+  // `import { type A, type B }` has no runtime binding either, but a default import always does.
+  const bindings = clause.namedBindings;
+  if (!clause.name && bindings && ts.isNamedImports(bindings) && bindings.elements.length > 0) {
+    return bindings.elements.every((element) => element.isTypeOnly) ? "type" : undefined;
+  }
+
+  return undefined;
+}
+
+/**
+ * Exports of local declarations as `[local, exported]` name pairs: separate statements such as
+ * `export { X }`, `export { X as Y }`, and `export default X`, and `export default class X {}`.
+ */
+function localExports(node: ts.Node, sourceFile: ts.SourceFile): [string, string][] {
+  if (ts.isExportDeclaration(node) && !node.moduleSpecifier && node.exportClause && ts.isNamedExports(node.exportClause)) {
+    return node.exportClause.elements.map((element) => [(element.propertyName ?? element.name).getText(sourceFile), element.name.getText(sourceFile)]);
+  }
+  if (ts.isExportAssignment(node) && ts.isIdentifier(node.expression)) {
+    return [[node.expression.text, node.isExportEquals ? node.expression.text : "default"]];
+  }
+  if ((ts.isFunctionDeclaration(node) || ts.isClassDeclaration(node)) && node.name && isDefaultExport(node)) {
+    return [[node.name.getText(sourceFile), "default"]];
+  }
+  return [];
+}
+
+/** Marks a symbol exported by a separate statement, and records the other names it is exported under. */
+function withLocalExports(symbol: SymbolNode, exports: [string, string][]): SymbolNode {
+  const names = exports.filter(([local]) => local === symbol.name).map(([, exported]) => exported);
+  if (names.length === 0) {
+    return symbol;
+  }
+
+  const aliases = Array.from(new Set(names.filter((name) => name !== symbol.name)));
+  return { ...symbol, exported: true, ...(aliases.length > 0 ? { exportedAs: aliases } : {}) };
 }
 
-function parseVariableStatement(node: ts.VariableStatement, sourceFile: ts.SourceFile): SymbolNode[] {
+function parseReExport(node: ts.ExportDeclaration, sourceFile: ts.SourceFile): ImportEdge | undefined {
+  if (!node.moduleSpecifier) {
+    return undefined;
+  }
+
+  const moduleName = node.moduleSpecifier.getText(sourceFile).replace(/["']/g, "");
+  const symbols: string[] = [];
+
+  if (!node.exportClause) {
+    symbols.push("*");
+  } else if (ts.isNamespaceExport(node.exportClause)) {
+    symbols.push(`* as ${node.exportClause.name.getText(sourceFile)}`);
+  } else {
+    node.exportClause.elements.forEach((element) => symbols.push(element.getText(sourceFile)));
+  }
+
+  return { from: moduleName, symbols, kind: "re-export" };
+}
+
+function withComplexity(symbol: SymbolNode, node: ts.Node, sourceFile: ts.SourceFile, options: ParserOptions): SymbolNode {
+  const functionLike = options.complexity ? functionLikeOf(node) : undefined;
+  return functionLike ? { ...symbol, ...measureComplexity(functionLike, sourceFile) } : symbol;
+}
+
+function implementedInterfaces(node: ts.ClassDeclaration): string[] {
+  return (node.heritageClauses ?? [])
+    .filter((clause) => clause.token === ts.SyntaxKind.ImplementsKeyword)
+    .flatMap((clause) => clause.types.map((type) => type.expression))
+    .map((expression) => (ts.isPropertyAccessExpression(expression) ? expression.name.text : ts.isIdentifier(expression) ? expression.text : ""))
+    .filter(Boolean);
+}
+
+function withMembers(symbol: SymbolNode, members: string[]): SymbolNode {
+  return members.length > 0 ? { ...symbol, members } : symbol;
+}
+
+function withRole(symbol: SymbolNode, node: ts.Node): SymbolNode {
+  const role = detectRole(symbol.name, node);
+  return role ? { ...symbol, role } : symbol;
+}
+
+function parseVariableStatement(node: ts.VariableStatement, sourceFile: ts.SourceFile, options: ParserOptions): SymbolNode[] {
   const exported = hasExportModifier(node.modifiers);
   const symbols: SymbolNode[] = [];
 
   node.declarationList.declarations.forEach((declaration) => {
     const name = declaration.name.getText(sourceFile);
-    symbols.push(createSymbolNode("variable", name, declaration, sourceFile, exported));
+    const symbol = createSymbolNode("variable", name, declaration, sourceFile, exported, options);
+    symbols.push(withComplexity(withRole(symbol, declaration), declaration, sourceFile, options));
   });
 
   return symbols;
 }
 
-export function parseSourceFile(filePath: string, root: string): FileGraph {
-  const content = fs.readFileSync(filePath, "utf8");
+function countParseErrors(sourceFile: ts.SourceFile): number {
+  // parseDiagnostics is populated by createSourceFile but is not part of the public typings.
+  const diagnostics = (sourceFile as ts.SourceFile & { parseDiagnostics?: readonly ts.Diagnostic[] }).parseDiagnostics;
+  return diagnostics?.length ?? 0;
+}
+
+function laterLocation(a: Location, b: Location): Location {
+  return b.line > a.line || (b.line === a.line && b.column > a.column) ? b : a;
+}
+
+function mergeSymbol(first: SymbolNode, next: SymbolNode): SymbolNode {
+  const merged: SymbolNode = { ...next, ...Object.fromEntries(Object.entries(first).filter(([, value]) => value !== undefined)) };
+  merged.exported = first.exported || next.exported;
+  if (first.end && next.end) {
+    merged.end = laterLocation(first.end, next.end);
+  }
+  // Overload signatures have no body, so the implementation's metrics are the meaningful ones.
+  if (next.complexity !== undefined) {
+    merged.complexity = Math.max(first.complexity ?? 0, next.complexity);
+    merged.loc = Math.max(first.loc ?? 0, next.loc ?? 0);
+  }
+  // A merged interface has the members of every declaration, and a merged class implements every interface listed.
+  if (first.members && next.members) {
+    merged.members = Array.from(new Set([...first.members, ...next.members]));
+  }
+  if (first.implements && next.implements) {
+    merged.implements = Array.from(new Set([...first.implements, ...next.implements]));
+  }
+  return merged;
+}
+
+/**
+ * Merges declarations sharing a name and kind, such as function overloads, re-opened namespaces, and
+ * merged interfaces, into the first of them, so every symbol appears once per file. Fields missing on
+ * the first declaration, like a doc comment on the implementation, are taken from the later ones.
+ */
+function mergeRedeclarations(symbols: SymbolNode[]): SymbolNode[] {
+  const merged: SymbolNode[] = [];
+  const byKey = new Map<string, number>();
+
+  for (const symbol of symbols) {
+    const key = `${symbol.kind}:${symbol.name}`;
+    const index = byKey.get(key);
+    if (index === undefined) {
+      byKey.set(key, merged.length);
+      merged.push(symbol);
+    } else {
+      merged[index] = mergeSymbol(merged[index], symbol);
+    }
+  }
+
+  return merged;
+}
+
+export function parseSourceFile(filePath: string, root: string, options: ParserOptions = {}): FileGraph {
+  return parseSource(fs.readFileSync(filePath, "utf8"), filePath, root, options);
+}
+
+export function parseSource(content: string, filePath: string, root: string, options: ParserOptions = {}): FileGraph {
   const sourceFile = ts.createSourceFile(filePath, content, ts.ScriptTarget.Latest, true);
   const imports: ImportEdge[] = [];
   const symbols: SymbolNode[] = [];
-  const relativePath = path.relative(root, filePath) || path.basename(filePath);
+  const exports: [string, string][] = [];
+  const relativePath = relativePosixPath(root, filePath);
 
   sourceFile.forEachChild((node) => {
+    exports.push(...localExports(node, sourceFile));
+
     if (ts.isImportDeclaration(node)) {
       imports.push(parseImport(node, sourceFile));
     }
 
-    if (ts.isFunctionDeclaration(node) && node.name) {
-      symbols.push(createSymbolNode("function", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers)));
+    if (ts.isExportDeclaration(node)) {
+      const edge = parseReExport(node, sourceFile);
+      if (edge) {
+        imports.push(edge);
+      }
     }
 
-    if (ts.isClassDeclaration(node) && node.name) {
-      symbols.push(createSymbolNode("class", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers)));
+    const declared = ts.isFunctionDeclaration(node) || ts.isClassDeclaration(node) ? declarationName(node, sourceFile) : undefined;
+    if (ts.isFunctionDeclaration(node) && declared) {
+      const symbol = createSymbolNode("function", declared, node, sourceFile, hasExportModifier(node.modifiers), options);
+      symbols.push(withComplexity(withRole(symbol, node), node, sourceFile, options));
+    }
+
+    if (ts.isClassDeclaration(node) && declared) {
+      const symbol = createSymbolNode("class", declared, node, sourceFile, hasExportModifier(node.modifiers), options);
+      const summary = summarizeClass(node, sourceFile, symbol.doc, options.classSummaries);
+      const interfaces = options.implementations ? implementedInterfaces(node) : [];
+      const members = options.members ? classMembers(node, sourceFile) : [];
+      symbols.push(withMembers({ ...symbol, ...(summary ? { summary } : {}), ...(interfaces.length > 0 ? { implements: interfaces } : {}) }, members));
     }
 
     if (ts.isInterfaceDeclaration(node)) {
-      symbols.push(createSymbolNode("interface", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers)));
+      const symbol = createSymbolNode("interface", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers), options);
+      symbols.push(withMembers(symbol, options.members ? interfaceMembers(node, sourceFile) : []));
     }
 
     if (ts.isTypeAliasDeclaration(node)) {
-      symbols.push(createSymbolNode("type", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers)));
+      symbols.push(createSymbolNode("type", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers), options));
     }
 
     if (ts.isEnumDeclaration(node)) {
-      symbols.push(createSymbolNode("enum", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers)));
+      symbols.push(createSymbolNode("enum", node.name.getText(sourceFile), node, sourceFile, hasExportModifier(node.modifiers), options));
+    }
+
+    // `declare module "pkg"` and `declare global` augment other modules rather than declaring a namespace.
+    if (ts.isModuleDeclaration(node) && ts.isIdentifier(node.name) && !(node.flags & ts.NodeFlags.GlobalAugmentation)) {
+      const name = namespaceName(node, sourceFile);
+      symbols.push(createSymbolNode("namespace", name, node, sourceFile, hasExportModifier(node.modifiers), options));
     }
 
     if (ts.isVariableStatement(node)) {
-      symbols.push(...parseVariableStatement(node, sourceFile));
+      symbols.push(...parseVariableStatement(node, sourceFile, options));
     }
   });
 
+  const parseErrors = countParseErrors(sourceFile);
+
   return {
     path: relativePath,
     imports,
-    symbols,
+    symbols: mergeRedeclarations(symbols).map((symbol) => withLocalExports(symbol, exports)),
+    ...(parseErrors > 0 ? { parseErrors } : {}),
   };
 }
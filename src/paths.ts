@@ -0,0 +1,17 @@
+import path from "path";
+
+/**
+ * Converts a filesystem path to the forward-slash form used everywhere in the graph,
+ * so output, anchors, and pattern matching are identical across operating systems.
+ */
+export function toPosixPath(filePath: string): string {
+  return filePath.split(path.sep).join("/").replace(/\\/g, "/");
+}
+
+/**
+ * Path of `filePath` relative to `root`, in forward-slash form. Falls back to the base name
+ * when the file is the root itself.
+ */
+export function relativePosixPath(root: string, filePath: string): string {
+  return toPosixPath(path.relative(root, filePath) || path.basename(filePath));
+}
@@ -0,0 +1,139 @@
+import { globToRegExp } from "./glob";
+import { CodeGraph, FileGraph, ImportEdge, SymbolNode } from "./types";
+
+export type QueryDirection = "out" | "in" | "both";
+
+export interface QueryOptions {
+  /** Import hops to expand from the files of the matched symbols (default: 1). */
+  depth?: number;
+  /** `out` follows imports, `in` follows importers, `both` follows either (default: both). */
+  direction?: QueryDirection;
+}
+
+export interface SymbolMatch {
+  file: string;
+  symbol: string;
+}
+
+function importedName(entry: string): string {
+  return entry.replace(/^type\s+/, "").split(/\s+as\s+/)[0];
+}
+
+function matchesSelector(file: FileGraph, symbol: SymbolNode, selector: string): "exact" | "loose" | undefined {
+  if (selector.includes("#")) {
+    return selector === `${file.path}#${symbol.name}` ? "exact" : undefined;
+  }
+  if (symbol.name === selector) {
+    return "exact";
+  }
+  if (/[*?]/.test(selector)) {
+    return globToRegExp(selector).test(symbol.name) ? "loose" : undefined;
+  }
+  return symbol.name.toLowerCase().includes(selector.toLowerCase()) ? "loose" : undefined;
+}
+
+/**
+ * Symbols matching any selector. A selector is a symbol id (`src/users.service.ts#UserService`), an exact
+ * name, a glob over names (`find*`), or a case-insensitive substring; substring and glob matches are only
+ * used for a selector that matches no symbol exactly.
+ */
+export function matchSymbols(graph: CodeGraph, selectors: string[]): SymbolMatch[] {
+  const matches: SymbolMatch[] = [];
+
+  for (const selector of selectors) {
+    const exact: SymbolMatch[] = [];
+    const loose: SymbolMatch[] = [];
+    for (const file of graph.files) {
+      for (const symbol of file.symbols) {
+        const match = matchesSelector(file, symbol, selector);
+        if (match) {
+          (match === "exact" ? exact : loose).push({ file: file.path, symbol: symbol.name });
+        }
+      }
+    }
+    matches.push(...(exact.length > 0 ? exact : loose));
+  }
+
+  return matches.filter((match, index) => matches.findIndex((other) => other.file === match.file && other.symbol === match.symbol) === index);
+}
+
+function neighborsOf(files: FileGraph[], direction: QueryDirection): Map<string, Set<string>> {
+  const neighbors = new Map<string, Set<string>>(files.map((file) => [file.path, new Set<string>()]));
+
+  for (const file of files) {
+    for (const edge of file.imports) {
+      if (!edge.resolved || !neighbors.has(edge.resolved)) {
+        continue;
+      }
+      if (direction !== "in") {
+        neighbors.get(file.path)?.add(edge.resolved);
+      }
+      if (direction !== "out") {
+        neighbors.get(edge.resolved)?.add(file.path);
+      }
+    }
+  }
+
+  return neighbors;
+}
+
+/**
+ * Extracts the part of a graph around the symbols matching `selectors`: the files declaring them and every
+ * file within `depth` import hops in the requested direction. Files of matched symbols keep only those
+ * symbols; reached files keep the symbols that kept files import from them, or become stubs when there
+ * are none. Imports between kept files are preserved, as are the external imports of matched files.
+ */
+export function querySubgraph(graph: CodeGraph, selectors: string[], options: QueryOptions = {}): CodeGraph {
+  const matches = matchSymbols(graph, selectors);
+  const seeds = new Set(matches.map((match) => match.file));
+  const neighbors = neighborsOf(graph.files, options.direction ?? "both");
+  const reached = new Set(seeds);
+  let frontier = Array.from(seeds);
+
+  for (let level = 0; level < (options.depth ?? 1); level += 1) {
+    const next: string[] = [];
+    for (const filePath of frontier) {
+      for (const neighbor of neighbors.get(filePath) ?? []) {
+        if (!reached.has(neighbor)) {
+          reached.add(neighbor);
+          next.push(neighbor);
+        }
+      }
+    }
+    frontier = next;
+  }
+
+  const keptImport = (file: FileGraph, edge: ImportEdge) => (edge.resolved ? reached.has(edge.resolved) : seeds.has(file.path));
+  const kept = graph.files.filter((file) => reached.has(file.path)).map((file) => ({ ...file, imports: file.imports.filter((edge) => keptImport(file, edge)) }));
+
+  const imported = new Map<string, Set<string>>();
+  for (const file of kept) {
+    for (const edge of file.imports) {
+      if (edge.resolved) {
+        const names = imported.get(edge.resolved) ?? new Set<string>();
+        edge.symbols.forEach((entry) => names.add(importedName(entry)));
+        imported.set(edge.resolved, names);
+      }
+    }
+  }
+
+  const files = kept.map((file): FileGraph => {
+    const wanted = (symbol: SymbolNode) =>
+      seeds.has(file.path)
+        ? matches.some((match) => match.file === file.path && match.symbol === symbol.name)
+        : symbol.exported && [symbol.name, ...(symbol.exportedAs ?? [])].some((name) => imported.get(file.path)?.has(name) ?? false);
+    const symbols = file.symbols.filter(wanted);
+    const importedBy = file.importedBy?.filter((importer) => kept.some((other) => other.path === importer && other.imports.some((edge) => edge.resolved === file.path)));
+    const dependsOn = file.dependsOn?.filter((target) => reached.has(target));
+    const { importedBy: _importedBy, dependsOn: _dependsOn, ...rest } = file;
+    return {
+      ...rest,
+      symbols,
+      ...(importedBy?.length ? { importedBy } : {}),
+      ...(dependsOn?.length ? { dependsOn } : {}),
+      ...(symbols.length === 0 && !seeds.has(file.path) ? { stub: true } : {}),
+    };
+  });
+
+  return { ...graph, files };
+}
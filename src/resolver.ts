@@ -0,0 +1,112 @@
+import fs from "fs";
+import path from "path";
+import ts from "typescript";
+import { toPosixPath } from "./paths";
+import { FileGraph } from "./types";
+
+const RESOLVABLE_EXTENSIONS = [".ts", ".tsx", ".d.ts", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs"];
+
+interface PathAlias {
+  prefix: string;
+  suffix: string;
+  wildcard: boolean;
+  targets: string[];
+}
+
+export interface ImportResolver {
+  /** Returns the graph path of the file `specifier` refers to from `fromFile`, or undefined for externals. */
+  resolve(fromFile: string, specifier: string): string | undefined;
+}
+
+function parsePattern(pattern: string): Pick<PathAlias, "prefix" | "suffix" | "wildcard"> {
+  const star = pattern.indexOf("*");
+  return star === -1
+    ? { prefix: pattern, suffix: "", wildcard: false }
+    : { prefix: pattern.slice(0, star), suffix: pattern.slice(star + 1), wildcard: true };
+}
+
+/**
+ * Reads `compilerOptions.paths` and `baseUrl` from a tsconfig file, following `extends` chains.
+ * Alias targets are returned as absolute paths.
+ */
+export function loadPathAliases(tsconfigPath: string): { aliases: PathAlias[]; baseUrl?: string } {
+  const { config, error } = ts.readConfigFile(tsconfigPath, ts.sys.readFile);
+  if (error || !config) {
+    return { aliases: [] };
+  }
+
+  const parsed = ts.parseJsonConfigFileContent(config, ts.sys, path.dirname(tsconfigPath));
+  const baseUrl = parsed.options.baseUrl;
+  const pathsBase = baseUrl ?? path.dirname(tsconfigPath);
+  const aliases = Object.entries(parsed.options.paths ?? {}).map(([pattern, targets]) => ({
+    ...parsePattern(pattern),
+    targets: targets.map((target) => path.resolve(pathsBase, target)),
+  }));
+
+  // Longest prefix first, matching TypeScript's own precedence.
+  aliases.sort((a, b) => b.prefix.length - a.prefix.length);
+  return { aliases, baseUrl };
+}
+
+export function createImportResolver(root: string, files: FileGraph[], tsconfigPath?: string): ImportResolver {
+  const normalizedRoot = path.resolve(root);
+  const known = new Set(files.map((file) => file.path));
+  const configPath = tsconfigPath ? path.resolve(tsconfigPath) : path.join(normalizedRoot, "tsconfig.json");
+  const { aliases, baseUrl } = fs.existsSync(configPath) ? loadPathAliases(configPath) : { aliases: [] as PathAlias[], baseUrl: undefined };
+
+  function probe(absoluteTarget: string): string | undefined {
+    const relative = toPosixPath(path.relative(normalizedRoot, absoluteTarget));
+    const withoutJsExtension = relative.replace(/\.[cm]?jsx?$/, "");
+    const candidates = [
+      relative,
+      ...RESOLVABLE_EXTENSIONS.map((extension) => `${withoutJsExtension}${extension}`),
+      ...RESOLVABLE_EXTENSIONS.map((extension) => `${relative}/index${extension}`),
+    ];
+    return candidates.find((candidate) => known.has(candidate));
+  }
+
+  function resolveAlias(specifier: string): string | undefined {
+    for (const alias of aliases) {
+      const matches = alias.wildcard
+        ? specifier.startsWith(alias.prefix) && specifier.endsWith(alias.suffix) && specifier.length >= alias.prefix.length + alias.suffix.length
+        : specifier === alias.prefix;
+      if (!matches) {
+        continue;
+      }
+
+      const captured = alias.wildcard ? specifier.slice(alias.prefix.length, specifier.length - alias.suffix.length) : "";
+      for (const target of alias.targets) {
+        const resolved = probe(target.replace("*", captured));
+        if (resolved) {
+          return resolved;
+        }
+      }
+    }
+
+    return baseUrl ? probe(path.resolve(baseUrl, specifier)) : undefined;
+  }
+
+  return {
+    resolve(fromFile: string, specifier: string): string | undefined {
+      if (specifier.startsWith(".")) {
+        return probe(path.resolve(normalizedRoot, path.dirname(fromFile), specifier));
+      }
+
+      return resolveAlias(specifier);
+    },
+  };
+}
+
+/**
+ * Sets `resolved` on every import edge that points at a file in the graph, including imports
+ * written with tsconfig path aliases such as `@app/users`.
+ */
+export function resolveImports(files: FileGraph[], resolver: ImportResolver): FileGraph[] {
+  return files.map((file) => ({
+    ...file,
+    imports: file.imports.map((edge) => {
+      const resolved = resolver.resolve(file.path, edge.from);
+      return resolved ? { ...edge, resolved } : edge;
+    }),
+  }));
+}
@@ -0,0 +1,28 @@
+import { CodeGraph } from "./types";
+
+/**
+ * Sets `importedBy` on every file imported by another file of the graph, listing the importers in path
+ * order. Run it after all filtering so the lists never name files that were removed.
+ */
+export function addReverseImports(graph: CodeGraph): CodeGraph {
+  const paths = new Set(graph.files.map((file) => file.path));
+  const importers = new Map<string, Set<string>>();
+
+  for (const file of graph.files) {
+    for (const edge of file.imports) {
+      if (edge.resolved && paths.has(edge.resolved)) {
+        const files = importers.get(edge.resolved) ?? new Set<string>();
+        files.add(file.path);
+        importers.set(edge.resolved, files);
+      }
+    }
+  }
+
+  return {
+    ...graph,
+    files: graph.files.map((file) => {
+      const importedBy = importers.get(file.path);
+      return importedBy ? { ...file, importedBy: Array.from(importedBy).sort() } : file;
+    }),
+  };
+}
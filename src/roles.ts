@@ -0,0 +1,58 @@
+import ts from "typescript";
+import { functionLikeOf } from "./complexity";
+import { NodeRole } from "./types";
+
+export const NODE_ROLES: readonly NodeRole[] = ["component", "hook", "hoc"];
+
+const COMPONENT_NAME = /^[A-Z][A-Za-z0-9]*$/;
+const HOOK_NAME = /^use[A-Z0-9]/;
+const HOC_NAME = /^with[A-Z]/;
+
+function containsNode(node: ts.Node, predicate: (candidate: ts.Node) => boolean): boolean {
+  return predicate(node) || Boolean(node.forEachChild((child) => (containsNode(child, predicate) ? true : undefined)));
+}
+
+function isJsx(node: ts.Node): boolean {
+  return ts.isJsxElement(node) || ts.isJsxSelfClosingElement(node) || ts.isJsxFragment(node);
+}
+
+function isHookCall(node: ts.Node): boolean {
+  if (!ts.isCallExpression(node)) {
+    return false;
+  }
+
+  const callee = node.expression;
+  const name = ts.isIdentifier(callee) ? callee.text : ts.isPropertyAccessExpression(callee) ? callee.name.text : "";
+  return HOOK_NAME.test(name);
+}
+
+function returnsFunction(body: ts.Node): boolean {
+  return containsNode(body, (node) => ts.isArrowFunction(node) || ts.isFunctionExpression(node) || ts.isClassExpression(node));
+}
+
+/**
+ * Classifies React functions by cheap structural checks: hooks are `use*` functions calling another
+ * hook, higher-order components are `with*` functions returning a function or class, and components
+ * are PascalCase functions rendering JSX.
+ */
+export function detectRole(name: string, node: ts.Node): NodeRole | undefined {
+  const functionLike = functionLikeOf(node);
+  const body = functionLike?.body;
+  if (!body) {
+    return undefined;
+  }
+
+  if (HOOK_NAME.test(name) && containsNode(body, isHookCall)) {
+    return "hook";
+  }
+
+  if (HOC_NAME.test(name) && returnsFunction(body)) {
+    return "hoc";
+  }
+
+  if (COMPONENT_NAME.test(name) && containsNode(body, isJsx)) {
+    return "component";
+  }
+
+  return undefined;
+}
@@ -0,0 +1,45 @@
+import fs from "fs";
+import path from "path";
+import ts from "typescript";
+import { CodeGraph, RunMetadata } from "./types";
+
+const TOOL_NAME = "ts-yaml-code-graph";
+
+function toolVersion(): string {
+  try {
+    return JSON.parse(fs.readFileSync(path.join(__dirname, "..", "package.json"), "utf8")).version ?? "unknown";
+  } catch {
+    return "unknown";
+  }
+}
+
+function isRecordable(key: string, value: unknown): boolean {
+  if (key === "cache" || key.startsWith("__") || value === undefined || typeof value === "function") {
+    return false;
+  }
+
+  return !(value && typeof value === "object" && !Array.isArray(value) && Object.keys(value).length === 0);
+}
+
+/**
+ * Attaches a `run` block describing how the graph was produced: tool and TypeScript versions, the
+ * options that were set (callbacks and caches excluded), and the resulting counts.
+ */
+export function embedRunMetadata(graph: CodeGraph, options: object): CodeGraph {
+  const recorded = Object.fromEntries(
+    Object.entries(options)
+      .filter(([key, value]) => isRecordable(key, value))
+      .sort(([a], [b]) => a.localeCompare(b))
+  );
+  const run: RunMetadata = {
+    tool: TOOL_NAME,
+    version: toolVersion(),
+    typescript: ts.version,
+    options: recorded,
+    files: graph.files.length,
+    symbols: graph.files.reduce((total, file) => total + file.symbols.length, 0),
+    imports: graph.files.reduce((total, file) => total + file.imports.length, 0),
+  };
+
+  return { ...graph, run };
+}
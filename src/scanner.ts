@@ -1,15 +1,42 @@
 import fs from "fs";
 import path from "path";
+import { isDeclarationFile } from "./declarations";
+import { isIgnored, loadIgnoreRules } from "./ignore";
+import { toPosixPath } from "./paths";
 
-const DEFAULT_EXTENSIONS = [".ts", ".tsx", ".js", ".jsx", ".mjs", ".cjs"];
-const IGNORED_DIRECTORIES = new Set(["node_modules", "dist", ".git", ".turbo", ".idea", ".vscode"]);
+export const DEFAULT_EXTENSIONS = [".ts", ".tsx", ".js", ".jsx", ".mjs", ".cjs"];
+export const IGNORED_DIRECTORIES = new Set(["node_modules", "dist", ".git", ".turbo", ".idea", ".vscode"]);
 
-export function collectSourceFiles(root: string, extensions?: string[]): string[] {
+export type SkipReason = "ignored-directory" | "ycgignore" | "extension";
+
+export interface SkippedEntry {
+  path: string;
+  reason: SkipReason;
+}
+
+export interface ScanResult {
+  files: string[];
+  skipped: SkippedEntry[];
+}
+
+/** Whether a forward-slash path relative to the root lies inside one of the `IGNORED_DIRECTORIES`. */
+export function isInIgnoredDirectory(relativePath: string): boolean {
+  return relativePath.split("/").slice(0, -1).some((segment) => IGNORED_DIRECTORIES.has(segment));
+}
+
+/**
+ * Lists the source files under `root`, skipping `IGNORED_DIRECTORIES` and `.ycgignore` matches. Ignored
+ * directories named in `declarationDirectories` (such as `dist`) are still reported as skipped, but their
+ * `.d.ts` files are collected so they can be merged into the implementation files they describe.
+ */
+export function scanSourceFiles(root: string, extensions?: string[], declarationDirectories: string[] = []): ScanResult {
   const normalizedRoot = path.resolve(root);
   const effectiveExtensions = extensions?.length ? extensions : DEFAULT_EXTENSIONS;
   const result: string[] = [];
+  const skipped: SkippedEntry[] = [];
+  const ignoreRules = loadIgnoreRules(normalizedRoot);
 
-  function walk(current: string): void {
+  function walk(current: string, declarationsOnly = false): void {
     const entries = fs.readdirSync(current, { withFileTypes: true });
 
     for (const entry of entries) {
@@ -18,17 +45,38 @@ export function collectSourceFiles(root: string, extensions?: string[]): string[
       }
 
       const fullPath = path.join(current, entry.name);
+      if (ignoreRules.length > 0 && isIgnored(toPosixPath(path.relative(normalizedRoot, fullPath)), ignoreRules)) {
+        skipped.push({ path: fullPath, reason: "ycgignore" });
+        continue;
+      }
+
       if (entry.isDirectory()) {
         if (IGNORED_DIRECTORIES.has(entry.name)) {
+          if (!declarationsOnly) {
+            skipped.push({ path: fullPath, reason: "ignored-directory" });
+          }
+          if (declarationDirectories.includes(entry.name)) {
+            walk(fullPath, true);
+          }
           continue;
         }
-        walk(fullPath);
+        walk(fullPath, declarationsOnly);
+      } else if (declarationsOnly) {
+        if (isDeclarationFile(entry.name) && effectiveExtensions.includes(path.extname(entry.name))) {
+          result.push(fullPath);
+        }
       } else if (effectiveExtensions.includes(path.extname(entry.name))) {
         result.push(fullPath);
+      } else {
+        skipped.push({ path: fullPath, reason: "extension" });
       }
     }
   }
 
   walk(normalizedRoot);
-  return result.sort();
+  return { files: result.sort(), skipped };
+}
+
+export function collectSourceFiles(root: string, extensions?: string[]): string[] {
+  return scanSourceFiles(root, extensions).files;
 }
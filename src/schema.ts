@@ -0,0 +1,41 @@
+import { CodeGraph } from "./types";
+
+/**
+ * Version of the graph format this release writes. Bump it, and add a migration below, whenever a change
+ * would make older readers misread new graphs.
+ */
+export const GRAPH_SCHEMA_VERSION = 2;
+
+/** Version assumed for graphs that declare none: everything written before versions were recorded. */
+export const UNVERSIONED_SCHEMA = 1;
+
+/** Rewrites a graph of the version it is keyed by into the structure of the next version. */
+const MIGRATIONS: Record<number, (graph: CodeGraph) => CodeGraph> = {
+  // Version 2 only started declaring `schemaVersion`; the structure is unchanged.
+  1: (graph) => graph,
+};
+
+/** Whether a graph was written by a newer release, whose fields and kinds this one may not know. */
+export function isNewerSchema(graph: CodeGraph): boolean {
+  return (graph.schemaVersion ?? UNVERSIONED_SCHEMA) > GRAPH_SCHEMA_VERSION;
+}
+
+/**
+ * Applies the migrations from the graph's declared version up to `toVersion` (default: the current one).
+ * Graphs already at or past `toVersion` are returned unchanged, so newer graphs keep their fields.
+ */
+export function migrateGraph(graph: CodeGraph, toVersion = GRAPH_SCHEMA_VERSION): CodeGraph {
+  let version = graph.schemaVersion ?? UNVERSIONED_SCHEMA;
+  let migrated = graph;
+
+  while (version < toVersion) {
+    const migrate = MIGRATIONS[version];
+    if (!migrate) {
+      throw new Error(`No migration from graph schema ${version} to ${version + 1}`);
+    }
+    migrated = migrate(migrated);
+    version += 1;
+  }
+
+  return { ...migrated, schemaVersion: version };
+}
@@ -0,0 +1,164 @@
+import { estimateTokens } from "./estimate";
+import { CodeGraph, FileGraph, SymbolKind, SymbolNode } from "./types";
+import { GRAPH_SECTIONS, SerializeOptions, serializeGraph } from "./yamlSerializer";
+
+export interface GraphStats {
+  files: number;
+  symbols: number;
+  symbolsByKind: Partial<Record<SymbolKind, number>>;
+  imports: { internal: number; external: number };
+  /** Files imported by the most other files, most imported first. */
+  mostImported: { path: string; importers: number }[];
+  /** Estimated tokens of the graph in each serialization. */
+  outputTokens: { yaml: number; shortKeys: number; shortKeysNoMetadata: number };
+  /** Estimated tokens of the source files the graph was built from, when known. */
+  sourceTokens?: number;
+  /** `outputTokens.yaml` divided by `sourceTokens`. */
+  compressionRatio?: number;
+}
+
+const MOST_IMPORTED_LIMIT = 10;
+
+/**
+ * Summarizes a graph: symbol counts by kind, internal and external import counts, the most imported
+ * files, and the estimated token size of each serialization.
+ */
+export function computeGraphStats(graph: CodeGraph, sourceTokens?: number): GraphStats {
+  const symbolsByKind: Partial<Record<SymbolKind, number>> = {};
+  const importers = new Map<string, Set<string>>();
+  let internal = 0;
+  let external = 0;
+
+  for (const file of graph.files) {
+    for (const symbol of file.symbols) {
+      symbolsByKind[symbol.kind] = (symbolsByKind[symbol.kind] ?? 0) + 1;
+    }
+
+    for (const edge of file.imports) {
+      if (!edge.resolved) {
+        external += 1;
+        continue;
+      }
+
+      internal += 1;
+      const files = importers.get(edge.resolved) ?? new Set<string>();
+      files.add(file.path);
+      importers.set(edge.resolved, files);
+    }
+  }
+
+  const mostImported = Array.from(importers.entries())
+    .map(([path, files]) => ({ path, importers: files.size }))
+    .sort((a, b) => b.importers - a.importers || a.path.localeCompare(b.path))
+    .slice(0, MOST_IMPORTED_LIMIT);
+
+  const yaml = estimateTokens(serializeGraph(graph));
+
+  return {
+    files: graph.files.length,
+    symbols: graph.files.reduce((total, file) => total + file.symbols.length, 0),
+    symbolsByKind,
+    imports: { internal, external },
+    mostImported,
+    outputTokens: {
+      yaml,
+      shortKeys: estimateTokens(serializeGraph(graph, { shortKeys: true })),
+      shortKeysNoMetadata: estimateTokens(serializeGraph(graph, { shortKeys: true, metadata: false })),
+    },
+    ...(sourceTokens ? { sourceTokens, compressionRatio: yaml / sourceTokens } : {}),
+  };
+}
+
+export interface TokenShare {
+  docs: number;
+  signatures: number;
+  imports: number;
+  /** Paths, names, kinds, locations, and the remaining fields. */
+  structure: number;
+}
+
+export interface TokenReport {
+  /** Estimated tokens of the whole output. */
+  total: number;
+  /** Tokens outside the file entries: metadata, `commonExternals`, `packages`, and section headers. */
+  envelope: number;
+  byCategory: TokenShare;
+  bySymbolKind: Partial<Record<SymbolKind, number>>;
+  /** Files with the largest entries, largest first. */
+  topFiles: { path: string; tokens: number }[];
+  /** Symbols with the largest entries as `<file>#<name>`, largest first. */
+  topSymbols: { id: string; kind: SymbolKind; tokens: number }[];
+}
+
+const TOKEN_REPORT_LIMIT = 20;
+
+/**
+ * Characters one file entry takes in the output, without the `files:` header. The `meta` section is left
+ * out, since `schemaVersion` is written even without metadata and belongs to the envelope.
+ */
+function fileEntryLength(graph: CodeGraph, file: FileGraph, options: SerializeOptions): number {
+  const sections = (options.sections ?? GRAPH_SECTIONS).filter((section) => section !== "meta");
+  const single = serializeGraph({ root: graph.root, generatedAt: graph.generatedAt, files: [file] }, { ...options, metadata: false, sections });
+  return single.length - (options.shortKeys ? "f:\n" : "files:\n").length;
+}
+
+function withoutDocs(symbol: SymbolNode): SymbolNode {
+  const { doc: _doc, summary: _summary, ...rest } = symbol;
+  return rest;
+}
+
+function withoutSignature(symbol: SymbolNode): SymbolNode {
+  const { signature: _signature, members: _members, ...rest } = symbol;
+  return rest;
+}
+
+/**
+ * Attributes the estimated output tokens of a graph to files, symbols, symbol kinds, and kinds of content,
+ * by serializing each file entry on its own and again with docs, signatures, imports, or one symbol
+ * removed. Lengths are summed before converting to tokens, so the file totals plus `envelope` equal `total`.
+ */
+export function computeTokenReport(graph: CodeGraph, options: SerializeOptions = {}): TokenReport {
+  const serialized = serializeGraph(graph, options);
+  const totalLength = serialized.length;
+  const share = { docs: 0, signatures: 0, imports: 0, files: 0 };
+  const kindLengths: Partial<Record<SymbolKind, number>> = {};
+  const files: { path: string; length: number }[] = [];
+  const symbols: { id: string; kind: SymbolKind; length: number }[] = [];
+
+  for (const file of graph.files) {
+    const length = fileEntryLength(graph, file, options);
+    files.push({ path: file.path, length });
+    share.files += length;
+    share.docs += length - fileEntryLength(graph, { ...file, symbols: file.symbols.map(withoutDocs) }, options);
+    share.signatures += length - fileEntryLength(graph, { ...file, symbols: file.symbols.map(withoutSignature) }, options);
+    share.imports += length - fileEntryLength(graph, { ...file, imports: [] }, options);
+
+    const bare = fileEntryLength(graph, { ...file, symbols: [] }, options);
+    for (const symbol of file.symbols) {
+      const symbolLength = fileEntryLength(graph, { ...file, symbols: [symbol] }, options) - bare;
+      symbols.push({ id: `${file.path}#${symbol.name}`, kind: symbol.kind, length: symbolLength });
+      kindLengths[symbol.kind] = (kindLengths[symbol.kind] ?? 0) + symbolLength;
+    }
+  }
+
+  const tokens = (length: number) => Math.round(length / 4);
+  return {
+    total: estimateTokens(serialized),
+    envelope: tokens(totalLength - share.files),
+    byCategory: {
+      docs: tokens(share.docs),
+      signatures: tokens(share.signatures),
+      imports: tokens(share.imports),
+      structure: tokens(share.files - share.docs - share.signatures - share.imports),
+    },
+    bySymbolKind: Object.fromEntries(Object.entries(kindLengths).map(([kind, length]) => [kind, tokens(length)])),
+    topFiles: files
+      .sort((a, b) => b.length - a.length || a.path.localeCompare(b.path))
+      .slice(0, TOKEN_REPORT_LIMIT)
+      .map((entry) => ({ path: entry.path, tokens: tokens(entry.length) })),
+    topSymbols: symbols
+      .sort((a, b) => b.length - a.length || a.id.localeCompare(b.id))
+      .slice(0, TOKEN_REPORT_LIMIT)
+      .map((entry) => ({ id: entry.id, kind: entry.kind, tokens: tokens(entry.length) })),
+  };
+}
@@ -0,0 +1,72 @@
+/** Node categories the visual exporters style independently. */
+export type NodeStyleKind = "file" | "declaration" | "stub" | "symbol" | "external";
+
+/** Edge categories: imports of project files and imports of external modules. */
+export type EdgeStyleKind = "internal" | "external";
+
+export type NodeShape = "circle" | "box" | "diamond";
+export type LineStyle = "solid" | "dashed" | "dotted";
+
+export interface Style {
+  color?: string;
+  shape?: NodeShape;
+  line?: LineStyle;
+  /** Leave matching elements out of the rendering; the graph itself is unchanged. */
+  hidden?: boolean;
+}
+
+export interface StyleConfig {
+  nodes?: Record<string, Style>;
+  edges?: Record<string, Style>;
+}
+
+export type ResolvedStyle = Required<Style>;
+
+export interface ResolvedStyles {
+  nodes: Record<NodeStyleKind, ResolvedStyle>;
+  edges: Record<EdgeStyleKind, ResolvedStyle>;
+}
+
+export const DEFAULT_STYLES: ResolvedStyles = {
+  nodes: {
+    file: { color: "#60a5fa", shape: "circle", line: "solid", hidden: false },
+    declaration: { color: "#a78bfa", shape: "diamond", line: "solid", hidden: false },
+    stub: { color: "#94a3b8", shape: "box", line: "dashed", hidden: false },
+    symbol: { color: "#34d399", shape: "circle", line: "solid", hidden: false },
+    external: { color: "#94a3b8", shape: "box", line: "dotted", hidden: false },
+  },
+  edges: {
+    internal: { color: "#60a5fa", shape: "circle", line: "solid", hidden: false },
+    external: { color: "#94a3b8", shape: "circle", line: "dotted", hidden: false },
+  },
+};
+
+function mergeSection<K extends string>(
+  defaults: Record<K, ResolvedStyle>,
+  overrides: Record<string, Style> | undefined,
+  section: string,
+  onWarning?: (message: string) => void
+): Record<K, ResolvedStyle> {
+  const merged = { ...defaults };
+  for (const [kind, style] of Object.entries(overrides ?? {})) {
+    if (!(kind in defaults)) {
+      onWarning?.(`Unknown ${section} style "${kind}" ignored (expected one of ${Object.keys(defaults).join(", ")})`);
+      continue;
+    }
+
+    merged[kind as K] = { ...defaults[kind as K], ...style };
+  }
+
+  return merged;
+}
+
+/**
+ * Applies a user style map over the built-in defaults. Unknown node kinds or edge types are reported
+ * through `onWarning` and skipped rather than rejected.
+ */
+export function resolveStyles(config: StyleConfig = {}, onWarning?: (message: string) => void): ResolvedStyles {
+  return {
+    nodes: mergeSection(DEFAULT_STYLES.nodes, config.nodes, "node", onWarning),
+    edges: mergeSection(DEFAULT_STYLES.edges, config.edges, "edge", onWarning),
+  };
+}
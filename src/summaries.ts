@@ -0,0 +1,102 @@
+import path from "path";
+import ts from "typescript";
+
+export type ClassSummaryMode = "heuristic" | "doc-only" | "off";
+
+export const CLASS_SUMMARY_MODES: readonly ClassSummaryMode[] = ["heuristic", "doc-only", "off"];
+
+const SUMMARY_MAX_LENGTH = 140;
+const MAX_LISTED_METHODS = 5;
+
+const DECORATOR_ROLES: Record<string, string> = {
+  Controller: "Controller",
+  Injectable: "Service",
+  Service: "Service",
+  Repository: "Repository",
+  EntityRepository: "Repository",
+  Entity: "Entity",
+  Module: "Module",
+  Resolver: "Resolver",
+  Component: "Component",
+};
+
+const PATH_ROLE_PATTERN = /\.(controller|service|repository|module|guard|entity|dto|resolver|component)\.[cm]?[jt]sx?$/;
+
+function capSummary(text: string): string {
+  return text.length <= SUMMARY_MAX_LENGTH ? text : `${text.slice(0, SUMMARY_MAX_LENGTH - 3)}...`;
+}
+
+export function firstSentence(text: string): string {
+  const flattened = text.replace(/\s+/g, " ").trim();
+  const match = flattened.match(/^.*?[.!?](?=\s|$)/);
+  return match ? match[0] : flattened;
+}
+
+function decoratorName(decorator: ts.Decorator): string | undefined {
+  const expression = ts.isCallExpression(decorator.expression) ? decorator.expression.expression : decorator.expression;
+  return ts.isIdentifier(expression) ? expression.text : undefined;
+}
+
+function inferRole(node: ts.ClassDeclaration, sourceFile: ts.SourceFile): string {
+  const decorators = (ts.canHaveDecorators(node) ? ts.getDecorators(node) : undefined) ?? [];
+  const fromDecorator = decorators
+    .map((decorator) => DECORATOR_ROLES[decoratorName(decorator) ?? ""])
+    .find((role): role is string => Boolean(role));
+  if (fromDecorator) {
+    return fromDecorator;
+  }
+
+  const fromPath = path.basename(sourceFile.fileName).match(PATH_ROLE_PATTERN)?.[1];
+  return fromPath ? fromPath.charAt(0).toUpperCase() + fromPath.slice(1) : "Class";
+}
+
+function isPublicMethod(member: ts.ClassElement): member is ts.MethodDeclaration {
+  if (!ts.isMethodDeclaration(member) || !ts.isIdentifier(member.name)) {
+    return false;
+  }
+
+  const modifiers = ts.canHaveModifiers(member) ? ts.getModifiers(member) : undefined;
+  return !modifiers?.some((modifier) => modifier.kind === ts.SyntaxKind.PrivateKeyword || modifier.kind === ts.SyntaxKind.ProtectedKeyword);
+}
+
+function synthesizeSummary(node: ts.ClassDeclaration, sourceFile: ts.SourceFile): string {
+  const role = inferRole(node, sourceFile);
+  const interfaces =
+    node.heritageClauses
+      ?.filter((clause) => clause.token === ts.SyntaxKind.ImplementsKeyword)
+      .flatMap((clause) => clause.types.map((type) => type.expression.getText(sourceFile))) ?? [];
+  const methods = node.members.filter(isPublicMethod).map((member) => member.name.getText(sourceFile));
+
+  let summary = role;
+  if (interfaces.length > 0) {
+    summary += ` implementing ${interfaces.join(", ")}`;
+  }
+  if (methods.length > 0) {
+    const listed = methods.slice(0, MAX_LISTED_METHODS).join(", ");
+    summary += ` exposing ${listed}${methods.length > MAX_LISTED_METHODS ? ", ..." : ""}`;
+  }
+
+  return summary;
+}
+
+/**
+ * Builds the one-sentence responsibility summary of a class: the first sentence of its documentation,
+ * or, in `heuristic` mode, a description synthesized from decorators, implemented interfaces,
+ * public method names, and the file name.
+ */
+export function summarizeClass(
+  node: ts.ClassDeclaration,
+  sourceFile: ts.SourceFile,
+  doc: string | undefined,
+  mode: ClassSummaryMode = "off"
+): string | undefined {
+  if (mode === "off") {
+    return undefined;
+  }
+
+  if (doc) {
+    return capSummary(firstSentence(doc));
+  }
+
+  return mode === "heuristic" ? capSummary(synthesizeSummary(node, sourceFile)) : undefined;
+}
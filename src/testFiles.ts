@@ -0,0 +1,22 @@
+import { matchesAnyGlob } from "./glob";
+import { FileGraph } from "./types";
+
+/** How test files are treated: kept as they are, left out of the graph, or kept and marked `test: true`. */
+export type TestFileMode = "include" | "exclude" | "tag";
+
+export const TEST_FILE_MODES: readonly TestFileMode[] = ["include", "exclude", "tag"];
+
+/**
+ * Paths treated as tests: the `.spec` and `.test` suffixes of Jest, Vitest, and Mocha, NestJS's
+ * `.e2e-spec` suffix, and the conventional `__tests__`, `test`, and `tests` directories.
+ */
+export const TEST_FILE_GLOBS = ["**/*.spec.*", "**/*.test.*", "**/*.e2e-spec.*", "**/__tests__/**", "**/test/**", "**/tests/**"];
+
+export function isTestFile(filePath: string): boolean {
+  return matchesAnyGlob(filePath, TEST_FILE_GLOBS);
+}
+
+/** Sets `test: true` on every file whose path looks like a test. */
+export function tagTestFiles(files: FileGraph[]): FileGraph[] {
+  return files.map((file) => (isTestFile(file.path) ? { ...file, test: true } : file));
+}
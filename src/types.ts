@@ -4,8 +4,11 @@ export type SymbolKind =
   | "interface"
   | "type"
   | "enum"
+  | "namespace"
   | "variable";
 
+export type NodeRole = "component" | "hook" | "hoc";
+
 export interface Location {
   line: number;
   column: number;
@@ -18,21 +21,90 @@ export interface SymbolNode {
   signature?: string;
   doc?: string;
   location: Location;
+  /** Where the declaration ends, set when ranges are enabled. */
+  end?: Location;
+  /** One-sentence responsibility summary, set on classes when class summaries are enabled. */
+  summary?: string;
+  /** React role of a function: component, hook, or higher-order component. */
+  role?: NodeRole;
+  /** Number of branching constructs in a function body, set when complexity metrics are enabled. */
+  complexity?: number;
+  /** Lines spanned by a function, set when complexity metrics are enabled. */
+  loc?: number;
+  /** Interfaces named in a class's `implements` clause, set when implementation resolution is enabled. */
+  implements?: string[];
+  /** Classes implementing an interface, as `<file>#<class>`, set when implementation resolution is enabled. */
+  implementedBy?: string[];
+  /** Compact signatures of the public methods and properties of a class or interface, set when member listing is enabled. */
+  members?: string[];
+  /** Other names the symbol is exported under: `default`, or `Y` for `export { X as Y }`. */
+  exportedAs?: string[];
 }
 
+/** How a module is referenced; plain value imports carry no kind. */
+export type ImportKind = "type" | "side-effect" | "re-export";
+
 export interface ImportEdge {
   from: string;
   symbols: string[];
+  kind?: ImportKind;
+  /** Graph path of the imported file when the specifier points inside the project. */
+  resolved?: string;
 }
 
 export interface FileGraph {
   path: string;
   imports: ImportEdge[];
   symbols: SymbolNode[];
+  /** Number of syntax errors the parser recovered from; omitted when the file parsed cleanly. */
+  parseErrors?: number;
+  /** Set on files kept only as neighbors of a focused selection, or added by `addImport` for a missing target; their symbols are omitted. */
+  stub?: boolean;
+  /** Files of the graph that import this one, set when reverse imports are enabled. */
+  importedBy?: string[];
+  /** Files of the graph this one imports, set when file dependencies are enabled. */
+  dependsOn?: string[];
+  /** Name of the workspace package containing the file, set when package detection is enabled. */
+  package?: string;
+  /** Set on test files when test files are tagged rather than included as they are or excluded. */
+  test?: boolean;
+}
+
+/** An external module whose import edges were replaced by a single count. */
+export interface CommonExternal {
+  module: string;
+  /** Number of distinct files importing the module. */
+  references: number;
+}
+
+/** A directory of the graph with its own `package.json`. */
+export interface WorkspacePackage {
+  name: string;
+  /** Directory relative to the graph root; `.` for the root itself. */
+  path: string;
+}
+
+/** How a graph was generated, recorded when run metadata is embedded. */
+export interface RunMetadata {
+  tool: string;
+  version: string;
+  typescript: string;
+  /** Options that were set for the run. */
+  options: Record<string, unknown>;
+  files: number;
+  symbols: number;
+  imports: number;
 }
 
 export interface CodeGraph {
+  /** Graph format version; set on graphs read back from disk, and always written by the serializer. */
+  schemaVersion?: number;
   root: string;
   generatedAt: string;
   files: FileGraph[];
+  /** Externals imported by more files than the noise threshold; their edges are omitted from `files`. */
+  commonExternals?: CommonExternal[];
+  /** Packages found when package detection is enabled. */
+  packages?: WorkspacePackage[];
+  run?: RunMetadata;
 }
@@ -0,0 +1,103 @@
+import { GRAPH_SCHEMA_VERSION, isNewerSchema } from "./schema";
+import { CodeGraph, SymbolKind } from "./types";
+
+export type ProblemLevel = "error" | "warning";
+
+export interface GraphProblem {
+  level: ProblemLevel;
+  message: string;
+  file?: string;
+}
+
+export const SYMBOL_KINDS: readonly SymbolKind[] = ["function", "class", "interface", "type", "enum", "namespace", "variable"];
+
+function isPositiveInteger(value: unknown): boolean {
+  return typeof value === "number" && Number.isInteger(value) && value >= 1;
+}
+
+/**
+ * Checks the structure of a graph: unique forward-slash file paths, well-formed imports and symbols, one
+ * symbol per name and kind in each file, imports whose `resolved` path names a file in the graph, and
+ * `importedBy` lists that match the imports. Imports resolved to files outside a focused or otherwise
+ * narrowed graph are reported as warnings, as are unknown symbol kinds in a graph of a newer schema.
+ */
+export function validateGraph(graph: CodeGraph): GraphProblem[] {
+  const problems: GraphProblem[] = [];
+  if (!Array.isArray(graph.files)) {
+    return [{ level: "error", message: "files must be a list" }];
+  }
+
+  const newer = isNewerSchema(graph);
+  if (newer) {
+    problems.push({ level: "warning", message: `written with graph schema ${graph.schemaVersion}, newer than ${GRAPH_SCHEMA_VERSION}; unknown fields are not checked` });
+  }
+
+  const paths = new Set<string>();
+  for (const file of graph.files) {
+    if (typeof file.path !== "string" || file.path === "") {
+      problems.push({ level: "error", message: "file entry without a path" });
+      continue;
+    }
+    if (paths.has(file.path)) {
+      problems.push({ level: "error", file: file.path, message: "duplicate file entry" });
+    }
+    if (file.path.includes("\\")) {
+      problems.push({ level: "error", file: file.path, message: "path contains backslashes" });
+    }
+    paths.add(file.path);
+  }
+
+  for (const file of graph.files.filter((candidate) => typeof candidate.path === "string" && candidate.path !== "")) {
+    for (const edge of file.imports ?? []) {
+      if (typeof edge.from !== "string" || !Array.isArray(edge.symbols)) {
+        problems.push({ level: "error", file: file.path, message: `malformed import ${JSON.stringify(edge)}` });
+      } else if (edge.resolved !== undefined && !paths.has(edge.resolved)) {
+        problems.push({ level: "warning", file: file.path, message: `import "${edge.from}" resolves to ${edge.resolved}, which is not in the graph` });
+      }
+    }
+
+    for (const target of file.dependsOn ?? []) {
+      if (!paths.has(target)) {
+        problems.push({ level: "warning", file: file.path, message: `dependsOn lists ${target}, which is not in the graph` });
+      }
+    }
+
+    for (const importer of file.importedBy ?? []) {
+      const forward = graph.files.find((candidate) => candidate.path === importer);
+      if (!forward?.imports?.some((edge) => edge.resolved === file.path)) {
+        problems.push({ level: "error", file: file.path, message: `importedBy lists ${importer}, which does not import this file` });
+      }
+    }
+
+    const declared = new Set<string>();
+    for (const symbol of file.symbols ?? []) {
+      const name = typeof symbol.name === "string" && symbol.name !== "" ? symbol.name : undefined;
+      if (!name) {
+        problems.push({ level: "error", file: file.path, message: "symbol without a name" });
+        continue;
+      }
+      if (declared.has(`${symbol.kind}:${name}`)) {
+        problems.push({ level: "error", file: file.path, message: `duplicate symbol ${name} (${String(symbol.kind)})` });
+      }
+      declared.add(`${symbol.kind}:${name}`);
+      if (!SYMBOL_KINDS.includes(symbol.kind)) {
+        // A graph from a newer release may use kinds this one does not know.
+        problems.push({ level: newer ? "warning" : "error", file: file.path, message: `symbol ${name} has unknown kind ${String(symbol.kind)}` });
+      }
+      if (typeof symbol.exported !== "boolean") {
+        problems.push({ level: "error", file: file.path, message: `symbol ${name} has no exported flag` });
+      }
+      if (!symbol.location || !isPositiveInteger(symbol.location.line) || !isPositiveInteger(symbol.location.column)) {
+        problems.push({ level: "error", file: file.path, message: `symbol ${name} has an invalid location` });
+      }
+      if (symbol.members !== undefined && !(Array.isArray(symbol.members) && symbol.members.every((member) => typeof member === "string"))) {
+        problems.push({ level: "error", file: file.path, message: `symbol ${name} has members that are not a list of signatures` });
+      }
+      if (symbol.exportedAs !== undefined && !(Array.isArray(symbol.exportedAs) && symbol.exportedAs.every((alias) => typeof alias === "string"))) {
+        problems.push({ level: "error", file: file.path, message: `symbol ${name} has exportedAs that is not a list of names` });
+      }
+    }
+  }
+
+  return problems;
+}
@@ -1,11 +1,16 @@
-import YAML from "yaml";
-import { CodeGraph } from "./types";
+import { isDeclarationFile } from "./declarations";
+import { EdgeStyleKind, LineStyle, NodeShape, NodeStyleKind, resolveStyles, StyleConfig } from "./styles";
+import { CodeGraph, FileGraph, ImportEdge } from "./types";
+import { deserializeGraph } from "./yamlSerializer";
 
 export type Theme = "dark" | "light";
 
 export interface VisualizationOptions {
   title?: string;
   theme?: Theme;
+  styles?: StyleConfig;
+  /** Receives warnings about style entries that match no node kind or edge type. */
+  onWarning?: (message: string) => void;
 }
 
 interface VisualizationNode {
@@ -14,12 +19,17 @@ interface VisualizationNode {
   imports: number;
   symbols: number;
   value: number;
+  color: string;
+  shape: NodeShape;
+  line: LineStyle;
 }
 
 interface VisualizationLink {
   source: string;
   target: string;
   weight: number;
+  color: string;
+  line: LineStyle;
 }
 
 interface VisualizationData {
@@ -32,19 +42,31 @@ function normalizeTheme(theme?: string): Theme {
 }
 
 export function parseGraphFromYaml(content: string): CodeGraph {
-  const parsed = YAML.parse(content);
-  if (!parsed || !Array.isArray(parsed.files)) {
-    throw new Error("Invalid graph YAML: missing files array");
+  return deserializeGraph(content);
+}
+
+export function nodeStyleKind(file: FileGraph): NodeStyleKind {
+  if (file.stub) {
+    return "stub";
   }
 
-  return parsed as CodeGraph;
+  return isDeclarationFile(file.path) ? "declaration" : "file";
+}
+
+export function edgeStyleKind(edge: ImportEdge): EdgeStyleKind {
+  return edge.resolved ? "internal" : "external";
 }
 
-export function buildVisualizationData(graph: CodeGraph): VisualizationData {
-  const nodes: VisualizationNode[] = graph.files.map((file) => {
+export function buildVisualizationData(graph: CodeGraph, options: VisualizationOptions = {}): VisualizationData {
+  const styles = resolveStyles(options.styles, options.onWarning);
+  const hiddenFiles = new Set(graph.files.filter((file) => styles.nodes[nodeStyleKind(file)].hidden).map((file) => file.path));
+  const visibleFiles = graph.files.filter((file) => !hiddenFiles.has(file.path));
+
+  const nodes: VisualizationNode[] = visibleFiles.map((file) => {
     const imports = file.imports.length;
     const symbols = file.symbols.length;
     const value = imports + symbols + 1;
+    const { color, shape, line } = styles.nodes[nodeStyleKind(file)];
 
     return {
       id: file.path,
@@ -52,21 +74,26 @@ export function buildVisualizationData(graph: CodeGraph): VisualizationData {
       imports,
       symbols,
       value,
+      color,
+      shape,
+      line,
     };
   });
 
   const links: VisualizationLink[] = [];
   const seen = new Set<string>();
 
-  for (const file of graph.files) {
+  for (const file of visibleFiles) {
     for (const edge of file.imports) {
-      const key = `${file.path}__${edge.from}`;
-      if (seen.has(key)) {
+      const target = edge.resolved ?? edge.from;
+      const key = `${file.path}__${target}`;
+      const style = styles.edges[edgeStyleKind(edge)];
+      if (seen.has(key) || style.hidden || hiddenFiles.has(target)) {
         continue;
       }
 
       seen.add(key);
-      links.push({ source: file.path, target: edge.from, weight: Math.max(edge.symbols.length, 1) });
+      links.push({ source: file.path, target, weight: Math.max(edge.symbols.length, 1), color: style.color, line: style.line });
     }
   }
 
@@ -76,7 +103,7 @@ export function buildVisualizationData(graph: CodeGraph): VisualizationData {
 export function generateGraphHtml(graph: CodeGraph, options: VisualizationOptions = {}): string {
   const theme = normalizeTheme(options.theme);
   const title = options.title ?? "Project dependency graph";
-  const data = buildVisualizationData(graph);
+  const data = buildVisualizationData(graph, options);
 
   const serializedData = JSON.stringify(data);
   const serializedMeta = JSON.stringify({
@@ -299,26 +326,18 @@ export function generateGraphHtml(graph: CodeGraph, options: VisualizationOption
       .attr("height", height)
       .attr("viewBox", \`0 0 \${width} \${height}\`);
 
-    const defs = svg.append("defs");
-    const gradient = defs
-      .append("linearGradient")
-      .attr("id", "edgeGradient")
-      .attr("x1", "0%")
-      .attr("x2", "100%")
-      .attr("y1", "0%")
-      .attr("y2", "0%");
-
-    gradient.append("stop").attr("offset", "0%").attr("stop-color", "var(--accent)");
-    gradient.append("stop").attr("offset", "100%").attr("stop-color", "var(--accent-2)");
+    const dashes = { solid: null, dashed: "6 4", dotted: "1 4" };
+    const shapes = { circle: d3.symbolCircle, box: d3.symbolSquare, diamond: d3.symbolDiamond };
 
     const link = svg
       .append("g")
-      .attr("stroke", "url(#edgeGradient)")
       .attr("stroke-linecap", "round")
       .selectAll("line")
       .data(data.links)
       .enter()
       .append("line")
+      .attr("stroke", (d) => d.color)
+      .attr("stroke-dasharray", (d) => dashes[d.line])
       .attr("stroke-width", (d) => Math.min(10, Math.sqrt(d.weight) + 1))
       .attr("opacity", 0.6);
 
@@ -348,10 +367,11 @@ export function generateGraphHtml(graph: CodeGraph, options: VisualizationOption
       );
 
     node
-      .append("circle")
-      .attr("r", (d) => Math.min(38, Math.sqrt(d.value) * 6 + 8))
+      .append("path")
+      .attr("d", (d) => d3.symbol().type(shapes[d.shape]).size(Math.PI * Math.min(38, Math.sqrt(d.value) * 6 + 8) ** 2)())
       .attr("fill", "var(--panel)")
-      .attr("stroke", "var(--accent)")
+      .attr("stroke", (d) => d.color)
+      .attr("stroke-dasharray", (d) => dashes[d.line])
       .attr("stroke-width", 1.6)
       .attr("opacity", 0.9)
       .on("mouseenter", (event, d) => showTooltip(event, d))
@@ -0,0 +1,174 @@
+import fs from "fs";
+import path from "path";
+import { DEFAULT_EXTENSIONS, IGNORED_DIRECTORIES } from "./scanner";
+
+/** Schedules `callback` after `delay` milliseconds and returns a function cancelling it. */
+export type Scheduler = (callback: () => void, delay: number) => () => void;
+
+export interface WatchLoopOptions {
+  /** Quiet period after the last change before regenerating (default: 200). */
+  debounceMs?: number;
+  /** Called when a run throws; the loop keeps going and retries on the next change. */
+  onError?: (error: Error) => void;
+  /** Replaces `setTimeout`, so the debounce can be driven by hand. */
+  schedule?: Scheduler;
+}
+
+export interface WatchLoop {
+  /** Records a change; the run starts once no further change arrives for the debounce period. */
+  notify(): void;
+  /** Runs immediately if a change is pending. */
+  flush(): void;
+  close(): void;
+}
+
+const DEFAULT_DEBOUNCE_MS = 200;
+
+const defaultScheduler: Scheduler = (callback, delay) => {
+  const timer = setTimeout(callback, delay);
+  return () => clearTimeout(timer);
+};
+
+/**
+ * Debounces change notifications into calls of `run`. Bursts of changes, such as a formatter rewriting
+ * many files, produce a single run, and a run that fails is reported without stopping the loop.
+ */
+export function createWatchLoop(run: () => void, options: WatchLoopOptions = {}): WatchLoop {
+  const schedule = options.schedule ?? defaultScheduler;
+  const delay = options.debounceMs ?? DEFAULT_DEBOUNCE_MS;
+  let cancel: (() => void) | undefined;
+  let closed = false;
+
+  const fire = () => {
+    cancel = undefined;
+    try {
+      run();
+    } catch (error) {
+      options.onError?.(error instanceof Error ? error : new Error(String(error)));
+    }
+  };
+
+  return {
+    notify() {
+      if (closed) {
+        return;
+      }
+      cancel?.();
+      cancel = schedule(fire, delay);
+    },
+    flush() {
+      if (cancel) {
+        cancel();
+        fire();
+      }
+    },
+    close() {
+      closed = true;
+      cancel?.();
+      cancel = undefined;
+    },
+  };
+}
+
+const WATCHED_CONFIG_FILES = new Set(["tsconfig.json", "package.json", ".ycgignore"]);
+
+export interface SourceWatchOptions {
+  extensions?: string[];
+  /** Absolute paths whose changes are ignored, such as the output files the run itself writes. */
+  exclude?: string[];
+}
+
+function isDirectory(target: string): boolean {
+  try {
+    return fs.statSync(target).isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Watches `root` and each directory below it separately, for platforms whose `fs.watch` has no recursive
+ * mode (Linux before Node.js 19.1). Directories created later are watched as soon as they appear, and
+ * `onEvent` receives paths relative to `root`, as the recursive watcher reports them.
+ */
+function watchEachDirectory(root: string, onEvent: (relative: string) => void): () => void {
+  const watchers = new Map<string, fs.FSWatcher>();
+
+  const watchTree = (directory: string) => {
+    if (watchers.has(directory)) {
+      return;
+    }
+
+    const watcher = fs.watch(directory, (_event, filename) => {
+      if (!filename) {
+        return;
+      }
+
+      const absolute = path.join(directory, filename.toString());
+      if (!IGNORED_DIRECTORIES.has(path.basename(absolute)) && isDirectory(absolute)) {
+        try {
+          watchTree(absolute);
+        } catch {
+          // Removed again before it could be watched; its parent reports the removal.
+        }
+      }
+      onEvent(path.relative(root, absolute));
+    });
+    // Raised when the directory itself is removed; its parent reports the removal.
+    watcher.on("error", () => {
+      watcher.close();
+      watchers.delete(directory);
+    });
+    watchers.set(directory, watcher);
+
+    for (const entry of fs.readdirSync(directory, { withFileTypes: true })) {
+      if (entry.isDirectory() && !IGNORED_DIRECTORIES.has(entry.name)) {
+        watchTree(path.join(directory, entry.name));
+      }
+    }
+  };
+
+  watchTree(root);
+  return () => {
+    watchers.forEach((watcher) => watcher.close());
+    watchers.clear();
+  };
+}
+
+/**
+ * Calls `onChange` for changes to source files and project configuration under `root`, skipping the
+ * directories the scanner skips. Falls back to one watcher per directory where recursive watching is
+ * unavailable. Returns a function that stops watching.
+ */
+export function watchSourceTree(root: string, onChange: (file: string) => void, options: SourceWatchOptions = {}): () => void {
+  const extensions = options.extensions?.length ? options.extensions : DEFAULT_EXTENSIONS;
+  const excluded = (options.exclude ?? []).map((entry) => path.resolve(entry));
+
+  const onEvent = (relative: string) => {
+    const absolute = path.resolve(root, relative);
+    if (relative.split(/[\\/]/).some((segment) => IGNORED_DIRECTORIES.has(segment))) {
+      return;
+    }
+    if (excluded.some((entry) => absolute === entry || absolute.startsWith(`${entry}${path.sep}`))) {
+      return;
+    }
+
+    if (extensions.includes(path.extname(absolute)) || WATCHED_CONFIG_FILES.has(path.basename(absolute))) {
+      onChange(absolute);
+    }
+  };
+
+  try {
+    const watcher = fs.watch(root, { recursive: true }, (_event, filename) => {
+      if (filename) {
+        onEvent(filename.toString());
+      }
+    });
+    return () => watcher.close();
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "ERR_FEATURE_UNAVAILABLE_ON_PLATFORM") {
+      throw error;
+    }
+    return watchEachDirectory(root, onEvent);
+  }
+}
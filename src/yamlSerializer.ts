@@ -1,6 +1,338 @@
 import YAML from "yaml";
-import { CodeGraph } from "./types";
+import { GRAPH_SCHEMA_VERSION, migrateGraph, UNVERSIONED_SCHEMA } from "./schema";
+import { CodeGraph, FileGraph, ImportEdge, SymbolNode } from "./types";
 
-export function serializeGraph(graph: CodeGraph): string {
-  return YAML.stringify(graph, { simpleKeys: true });
+/**
+ * Parts of the graph that can be left out of the output: symbol inventories, import edges, file-level
+ * `dependsOn` lists, and metadata.
+ */
+export type GraphSection = "symbols" | "imports" | "dependencies" | "meta";
+
+export const GRAPH_SECTIONS: readonly GraphSection[] = ["symbols", "imports", "dependencies", "meta"];
+
+export interface SerializeOptions {
+  /** Use one- to three-character field names to reduce envelope overhead. */
+  shortKeys?: boolean;
+  /** Drop `root` and `generatedAt` for pipelines that track provenance externally. */
+  metadata?: boolean;
+  /** Sections to write (default: all). */
+  sections?: GraphSection[];
+}
+
+type KeyMap = Record<string, string>;
+
+const GRAPH_KEYS: KeyMap = { schemaVersion: "v", root: "r", generatedAt: "t", files: "f", commonExternals: "ce", packages: "pks" };
+const FILE_KEYS: KeyMap = { path: "p", imports: "i", symbols: "s", parseErrors: "pe", stub: "st", importedBy: "ib", dependsOn: "dep", package: "pk", test: "tst" };
+const IMPORT_KEYS: KeyMap = { from: "m", symbols: "s", kind: "k", resolved: "to" };
+const SYMBOL_KEYS: KeyMap = { name: "n", kind: "k", exported: "x", signature: "sig", doc: "doc", location: "l", summary: "sum", role: "ro", complexity: "cx", loc: "loc", end: "e", implements: "im", implementedBy: "imb", members: "mb", exportedAs: "xa" };
+const LOCATION_KEYS: KeyMap = { line: "ln", column: "col" };
+const EXTERNAL_KEYS: KeyMap = { module: "m", references: "c" };
+const PACKAGE_KEYS: KeyMap = { name: "n", path: "p" };
+
+function renameKeys(value: object, keys: KeyMap): Record<string, unknown> {
+  const result: Record<string, unknown> = {};
+  for (const [key, entry] of Object.entries(value)) {
+    result[keys[key] ?? key] = entry;
+  }
+
+  return result;
+}
+
+function invert(keys: KeyMap): KeyMap {
+  return Object.fromEntries(Object.entries(keys).map(([long, short]) => [short, long]));
+}
+
+function shortenSymbol(symbol: SymbolNode): Record<string, unknown> {
+  return renameKeys(
+    {
+      ...symbol,
+      location: renameKeys(symbol.location, LOCATION_KEYS),
+      ...(symbol.end ? { end: renameKeys(symbol.end, LOCATION_KEYS) } : {}),
+    },
+    SYMBOL_KEYS
+  );
+}
+
+function shortenFile(file: FileGraph): Record<string, unknown> {
+  return renameKeys(
+    {
+      ...file,
+      ...(file.imports ? { imports: file.imports.map((edge) => renameKeys(edge, IMPORT_KEYS)) } : {}),
+      ...(file.symbols ? { symbols: file.symbols.map(shortenSymbol) } : {}),
+    },
+    FILE_KEYS
+  );
+}
+
+/**
+ * Removes the sections not listed in `sections` from a graph. Files keep empty `symbols` and `imports`
+ * lists, so the result is still a complete graph for renderers other than YAML.
+ */
+export function selectSections(graph: CodeGraph, sections: readonly GraphSection[] = GRAPH_SECTIONS): CodeGraph {
+  if (GRAPH_SECTIONS.every((section) => sections.includes(section))) {
+    return graph;
+  }
+
+  const { run: _run, commonExternals, ...rest } = graph;
+  const keepImports = sections.includes("imports");
+  return {
+    ...rest,
+    ...(sections.includes("meta") ? { root: graph.root, generatedAt: graph.generatedAt, ...(graph.run ? { run: graph.run } : {}) } : { root: "", generatedAt: "" }),
+    ...(keepImports && commonExternals ? { commonExternals } : {}),
+    files: graph.files.map((file) => {
+      const { importedBy, dependsOn, ...fileRest } = file;
+      return {
+        ...fileRest,
+        symbols: sections.includes("symbols") ? file.symbols : [],
+        imports: keepImports ? file.imports : [],
+        ...(keepImports && importedBy ? { importedBy } : {}),
+        ...(sections.includes("dependencies") && dependsOn ? { dependsOn } : {}),
+      };
+    }),
+  };
+}
+
+/** Drops the keys of left-out sections entirely, rather than writing them as empty lists. */
+function omitSections(file: FileGraph, sections: readonly GraphSection[]): FileGraph {
+  const { symbols, imports, ...rest } = file;
+  return {
+    ...rest,
+    ...(sections.includes("imports") ? { imports } : {}),
+    ...(sections.includes("symbols") ? { symbols } : {}),
+  } as FileGraph;
+}
+
+export class SerializationError extends Error {
+  readonly section: string;
+  readonly file?: string;
+  readonly symbol?: string;
+
+  constructor(message: string, section: string, file?: string, symbol?: string) {
+    super(message);
+    this.name = "SerializationError";
+    this.section = section;
+    this.file = file;
+    this.symbol = symbol;
+  }
+}
+
+function stringify(value: unknown): string {
+  return YAML.stringify(value, { simpleKeys: true });
+}
+
+function failsToSerialize(value: unknown): boolean {
+  try {
+    stringify(value);
+    return false;
+  } catch {
+    return true;
+  }
+}
+
+/** Binary search for the first item that fails to serialize on its own. */
+function bisectFailure<T>(items: T[]): T | undefined {
+  let candidates = items;
+  while (candidates.length > 1) {
+    const half = candidates.slice(0, Math.ceil(candidates.length / 2));
+    candidates = failsToSerialize(half) ? half : candidates.slice(half.length);
+  }
+
+  return candidates.length === 1 && failsToSerialize(candidates) ? candidates[0] : undefined;
+}
+
+function findNonFiniteNumber(value: unknown, trail: string): string | undefined {
+  if (typeof value === "number") {
+    return Number.isFinite(value) ? undefined : trail;
+  }
+
+  if (value && typeof value === "object") {
+    for (const [key, entry] of Object.entries(value)) {
+      const found = findNonFiniteNumber(entry, trail ? `${trail}.${key}` : key);
+      if (found) {
+        return found;
+      }
+    }
+  }
+
+  return undefined;
+}
+
+/**
+ * Rejects values that would serialize to something consumers cannot read back as numbers.
+ */
+function checkSerializable(graph: CodeGraph): void {
+  for (const file of graph.files) {
+    for (const symbol of file.symbols) {
+      const field = findNonFiniteNumber(symbol, "");
+      if (field) {
+        throw new SerializationError(`Non-finite number in field "${field}"`, "files", file.path, symbol.name);
+      }
+    }
+  }
+}
+
+/**
+ * Re-serializes the metadata and each file separately to pinpoint what made serialization fail.
+ */
+function explainFailure(graph: CodeGraph, error: Error): SerializationError {
+  const { files, ...metadata } = graph;
+  if (failsToSerialize(metadata)) {
+    return new SerializationError(`Failed to serialize graph metadata: ${error.message}`, "metadata");
+  }
+
+  const file = bisectFailure(files);
+  if (!file) {
+    return new SerializationError(`Failed to serialize graph: ${error.message}`, "files");
+  }
+
+  const symbol = bisectFailure(file.symbols);
+  const where = symbol ? `symbol "${symbol.name}" in ${file.path}` : file.path;
+  return new SerializationError(`Failed to serialize ${where}: ${error.message}`, "files", file.path, symbol?.name);
+}
+
+export function serializeGraph(graph: CodeGraph, options: SerializeOptions = {}): string {
+  const chunks: string[] = [];
+  writeGraph(graph, options, (chunk) => chunks.push(chunk));
+  return chunks.join("");
+}
+
+/**
+ * Serializes the graph in chunks, one per top-level field and one per file, so callers can stream
+ * large graphs to disk without building the whole document in memory. The concatenated chunks are
+ * exactly what `serializeGraph` returns.
+ */
+export function writeGraph(graph: CodeGraph, options: SerializeOptions, write: (chunk: string) => void): void {
+  checkSerializable(graph);
+
+  try {
+    emitDocument(buildDocument(graph, options), options.shortKeys ? GRAPH_KEYS.files : "files", write);
+  } catch (error) {
+    throw explainFailure(graph, error as Error);
+  }
+}
+
+/** Emits a top-level mapping key by key, and the non-empty `filesKey` sequence item by item. */
+function emitDocument(document: Record<string, unknown>, filesKey: string, write: (chunk: string) => void): void {
+  const sequenceHeader = `${filesKey}:\n`;
+
+  for (const [key, value] of Object.entries(document)) {
+    if (key !== filesKey || !Array.isArray(value) || value.length === 0) {
+      write(stringify({ [key]: value }));
+      continue;
+    }
+
+    write(sequenceHeader);
+    for (const item of value) {
+      write(stringify({ [key]: [item] }).slice(sequenceHeader.length));
+    }
+  }
+}
+
+function buildDocument(graph: CodeGraph, options: SerializeOptions): Record<string, unknown> {
+  const sections = options.sections ?? GRAPH_SECTIONS;
+  const selected = selectSections(graph, sections);
+  const metadata = options.metadata !== false && sections.includes("meta");
+  const files = selected.files.map((file) => omitSections(file, sections));
+  const { schemaVersion, root, generatedAt, ...rest } = selected;
+  // A graph read from a newer schema keeps declaring it, since its unknown fields are written back as they are.
+  const version = Math.max(schemaVersion ?? GRAPH_SCHEMA_VERSION, GRAPH_SCHEMA_VERSION);
+  // The version is kept with --no-metadata: unlike root and generatedAt it does not change between runs.
+  const versioned = sections.includes("meta") ? { schemaVersion: version } : {};
+  const document: Record<string, unknown> = metadata ? { ...versioned, root, generatedAt, ...rest, files } : { ...versioned, ...rest, files };
+
+  if (!options.shortKeys) {
+    return document;
+  }
+
+  const shortened = renameKeys(
+    {
+      ...document,
+      files: files.map(shortenFile),
+      ...(selected.commonExternals ? { commonExternals: selected.commonExternals.map((external) => renameKeys(external, EXTERNAL_KEYS)) } : {}),
+      ...(selected.packages ? { packages: selected.packages.map((entry) => renameKeys(entry, PACKAGE_KEYS)) } : {}),
+    },
+    GRAPH_KEYS
+  );
+  return metadata ? { _meta: { keys: "short" }, ...shortened } : shortened;
+}
+
+function expandFile(raw: Record<string, unknown>): FileGraph {
+  const file = renameKeys(raw, invert(FILE_KEYS)) as Record<string, unknown>;
+  const imports = (file.imports as Record<string, unknown>[] | undefined) ?? [];
+  const symbols = (file.symbols as Record<string, unknown>[] | undefined) ?? [];
+
+  return {
+    ...file,
+    imports: imports.map((edge) => renameKeys(edge, invert(IMPORT_KEYS)) as unknown as ImportEdge),
+    symbols: symbols.map((symbol) => {
+      const expanded = renameKeys(symbol, invert(SYMBOL_KEYS));
+      return {
+        ...expanded,
+        location: renameKeys(expanded.location as object, invert(LOCATION_KEYS)),
+        ...(expanded.end ? { end: renameKeys(expanded.end as object, invert(LOCATION_KEYS)) } : {}),
+      } as unknown as SymbolNode;
+    }),
+  } as unknown as FileGraph;
+}
+
+/** A graph read back from YAML, with the form it was written in. */
+export interface GraphDocument {
+  graph: CodeGraph;
+  /** Whether the document used short keys. */
+  shortKeys: boolean;
+  /** Whether the document carried `root` and `generatedAt`, which `--no-metadata` leaves out. */
+  metadata: boolean;
+  /** Schema version the document declared before migration, or `UNVERSIONED_SCHEMA` when it declared none. */
+  schemaVersion: number;
+}
+
+/**
+ * Parses a serialized graph, accepting both the long and the short key forms. Files written without the
+ * `symbols` or `imports` section read back with empty lists. Graphs of an older schema are migrated to
+ * the current one; graphs of a newer schema are read as they are, keeping fields this version does not know.
+ */
+export function deserializeGraph(content: string): CodeGraph {
+  return readGraphDocument(content).graph;
+}
+
+/** Like `deserializeGraph`, also reporting the key form, metadata, and schema version the document was written with. */
+export function readGraphDocument(content: string): GraphDocument {
+  const parsed = YAML.parse(content);
+  if (!parsed || typeof parsed !== "object") {
+    throw new Error("Invalid graph YAML: expected a mapping");
+  }
+
+  const { _meta: _ignored, ...body } = parsed as Record<string, unknown>;
+  const shortForm = Array.isArray(body.f) && !Array.isArray(body.files);
+  const graph = shortForm ? renameKeys(body, invert(GRAPH_KEYS)) : body;
+
+  if (!Array.isArray(graph.files)) {
+    throw new Error("Invalid graph YAML: missing files array");
+  }
+
+  const schemaVersion = graph.schemaVersion;
+  if (schemaVersion !== undefined && !(typeof schemaVersion === "number" && Number.isInteger(schemaVersion) && schemaVersion >= 1)) {
+    throw new Error(`Invalid graph YAML: schemaVersion must be a positive integer, got ${JSON.stringify(schemaVersion)}`);
+  }
+
+  const commonExternals = graph.commonExternals as Record<string, unknown>[] | undefined;
+  const packages = graph.packages as Record<string, unknown>[] | undefined;
+
+  const migrated = migrateGraph({
+    ...graph,
+    ...(shortForm && commonExternals ? { commonExternals: commonExternals.map((external) => renameKeys(external, invert(EXTERNAL_KEYS))) } : {}),
+    ...(shortForm && packages ? { packages: packages.map((entry) => renameKeys(entry, invert(PACKAGE_KEYS))) } : {}),
+    root: (graph.root as string | undefined) ?? "",
+    generatedAt: (graph.generatedAt as string | undefined) ?? "",
+    files: shortForm
+      ? (graph.files as Record<string, unknown>[]).map(expandFile)
+      : (graph.files as FileGraph[]).map((file) => ({ ...file, imports: file.imports ?? [], symbols: file.symbols ?? [] })),
+  } as unknown as CodeGraph);
+
+  return {
+    graph: migrated,
+    shortKeys: shortForm,
+    metadata: graph.root !== undefined || graph.generatedAt !== undefined,
+    schemaVersion: (schemaVersion as number | undefined) ?? UNVERSIONED_SCHEMA,
+  };
 }
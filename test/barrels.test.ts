@@ -0,0 +1,58 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+import { buildGraph } from "../src/graph";
+import { writeProject } from "./helpers";
+
+function importsOf(root: string, filePath: string) {
+  const graph = buildGraph(root, { collapseBarrels: true });
+  assert.ok(!graph.files.some((file) => file.path === "src/index.ts"), "the barrel is removed");
+  return graph.files.find((file) => file.path === filePath)?.imports ?? [];
+}
+
+test("a default import follows export { default } to the declaring file", () => {
+  const root = writeProject({
+    "src/foo.ts": "export default class Foo {}\n",
+    "src/index.ts": 'export { default } from "./foo";\n',
+    "src/app.ts": 'import Foo from "./index";\nexport const app = new Foo();\n',
+  });
+
+  assert.deepEqual(importsOf(root, "src/app.ts"), [{ from: "./index", symbols: ["default as Foo"], resolved: "src/foo.ts" }]);
+});
+
+test("a default re-exported under a name is traced to its module", () => {
+  const root = writeProject({
+    "src/config.ts": "export default { port: 80 };\n",
+    "src/index.ts": 'export { default as config } from "./config";\n',
+    "src/app.ts": 'import { config } from "./index";\nexport const port = config.port;\n',
+  });
+
+  assert.deepEqual(importsOf(root, "src/app.ts"), [{ from: "./index", symbols: ["config"], resolved: "src/config.ts" }]);
+});
+
+test("an aliased local export is found through export *", () => {
+  const root = writeProject({
+    "src/values.ts": "const value = 1;\nexport { value as renamed };\n",
+    "src/other.ts": "export const other = 2;\n",
+    "src/index.ts": 'export * from "./other";\nexport * from "./values";\n',
+    "src/app.ts": 'import { other, renamed } from "./index";\nexport const sum = other + renamed;\n',
+  });
+
+  assert.deepEqual(importsOf(root, "src/app.ts"), [
+    { from: "./index", symbols: ["other"], resolved: "src/other.ts" },
+    { from: "./index", symbols: ["renamed"], resolved: "src/values.ts" },
+  ]);
+});
+
+test("files that both declare and re-export are followed", () => {
+  const root = writeProject({
+    "src/core.ts": "export function core(): void {}\n",
+    "src/api.ts": 'export * from "./core";\nexport const api = 1;\n',
+    "src/index.ts": 'export * from "./api";\n',
+    "src/app.ts": 'import { api, core } from "./index";\nexport const both = [api, core];\n',
+  });
+
+  assert.deepEqual(importsOf(root, "src/app.ts"), [
+    { from: "./index", symbols: ["api"], resolved: "src/api.ts" },
+    { from: "./index", symbols: ["core"], resolved: "src/core.ts" },
+  ]);
+});
@@ -0,0 +1,60 @@
+import assert from "node:assert/strict";
+import fs from "fs";
+import path from "path";
+import test from "node:test";
+import { ConfigError, loadConfigFile, validateConfig } from "../src/config";
+import { writeProject } from "./helpers";
+
+function configFile(content: unknown): string {
+  const root = writeProject({ "tools/ycg.config.json": JSON.stringify(content) });
+  return path.join(root, "tools", "ycg.config.json");
+}
+
+function assertConfigError(action: () => unknown, message: RegExp): void {
+  assert.throws(action, (error: unknown) => error instanceof ConfigError && error.kind === "invalid-value" && message.test(error.message));
+}
+
+test("unsupported values are rejected with the supported ones as a hint", () => {
+  assert.throws(
+    () => validateConfig({ includeKinds: ["class", "widget" as never] }),
+    (error: unknown) => error instanceof ConfigError && /widget/.test(error.message) && /function/.test(error.suggestion ?? "")
+  );
+  assertConfigError(() => validateConfig({ format: "pdf" as never }), /--format/);
+  assertConfigError(() => validateConfig({ sections: "meta" as never }), /must be a list/);
+});
+
+test("conflicting values are rejected", () => {
+  assert.throws(
+    () => validateConfig({ includeKinds: ["class"], excludeKinds: ["class"] }),
+    (error: unknown) => error instanceof ConfigError && error.kind === "conflicting-flags"
+  );
+});
+
+test("unknown keys in a configuration file are rejected by name", () => {
+  assertConfigError(() => loadConfigFile(configFile({ shortkeys: true })), /Unknown key "shortkeys"/);
+  assert.throws(
+    () => loadConfigFile(configFile({ shortkeys: true })),
+    (error: unknown) => error instanceof ConfigError && error.suggestion === 'Did you mean "shortKeys"?'
+  );
+});
+
+test("values of the wrong type in a configuration file are rejected by key", () => {
+  assertConfigError(() => loadConfigFile(configFile({ extensions: ".ts" })), /"extensions" .* must be a string list/);
+  assertConfigError(() => loadConfigFile(configFile({ shortKeys: "yes" })), /"shortKeys" .* must be a boolean/);
+  assertConfigError(() => loadConfigFile(configFile({ focusDepth: "2" })), /"focusDepth" .* must be a number/);
+});
+
+test("relative paths are resolved against the configuration file", () => {
+  const file = configFile({ root: "..", out: "graph.yaml", cacheDir: ".cache" });
+  const directory = path.dirname(file);
+  const config = loadConfigFile(file);
+
+  assert.equal(config.root, path.dirname(directory));
+  assert.equal(config.out, path.join(directory, "graph.yaml"));
+  assert.equal(config.cacheDir, path.join(directory, ".cache"));
+  assert.ok(fs.existsSync(config.root));
+});
+
+test("writing to stdout is left as it is", () => {
+  assert.equal(loadConfigFile(configFile({ out: "-" })).out, "-");
+});
@@ -0,0 +1,65 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+import { diffGraphs } from "../src/diff";
+import { buildGraph } from "../src/graph";
+import { writeProject } from "./helpers";
+
+const DB = "export const db = { get: (id: string) => Promise.resolve(id) };\n";
+
+function diffProjects(before: Record<string, string>, after: Record<string, string>, renameThreshold?: number) {
+  return diffGraphs(buildGraph(writeProject(before)), buildGraph(writeProject(after)), { renameThreshold });
+}
+
+test("a renamed function keeping its signature and doc is reported as renamed", () => {
+  const body = (name: string) =>
+    `import { db } from "./db";\n/** Loads a user by id from the database. */\nexport function ${name}(id: string): Promise<string> {\n  return db.get(id);\n}\n`;
+  const diff = diffProjects({ "src/db.ts": DB, "src/users.ts": body("loadUser") }, { "src/db.ts": DB, "src/users.ts": body("fetchUser") });
+
+  assert.equal(diff.symbols.length, 1);
+  const [change] = diff.symbols;
+  assert.equal(change.change, "renamed");
+  assert.equal(change.name, "fetchUser");
+  assert.equal(change.previousName, "loadUser");
+  assert.equal(change.from, undefined);
+  assert.ok((change.score ?? 0) >= 0.75);
+  assert.equal(change.evidence?.signature, 1);
+  assert.equal(change.evidence?.doc, 1);
+});
+
+test("a class moved to another file is reported as moved", () => {
+  const service = "export class UserService {\n  find(id: string): string {\n    return id;\n  }\n}\n";
+  const diff = diffProjects({ "src/a.ts": service }, { "src/services/user.ts": service });
+
+  assert.deepEqual(
+    diff.symbols.map((change) => [change.change, change.name, change.from, change.file]),
+    [["moved", "UserService", "src/a.ts", "src/services/user.ts"]]
+  );
+});
+
+test("unrelated symbols removed and added are not paired at the default threshold", () => {
+  const diff = diffProjects(
+    { "src/db.ts": DB, "src/util.ts": 'import { db } from "./db";\nexport function parseDate(input: string): Date {\n  return new Date(input);\n}\n' },
+    {
+      "src/db.ts": DB,
+      "src/util.ts": 'import { db } from "./db";\nexport function formatName(first: string, last: string): string {\n  return `${first} ${last}`;\n}\n',
+    }
+  );
+
+  assert.deepEqual(
+    diff.symbols.map((change) => [change.change, change.name]).sort(),
+    [
+      ["added", "formatName"],
+      ["removed", "parseDate"],
+    ]
+  );
+});
+
+test("a threshold above 1 turns rename detection off", () => {
+  const source = (name: string) => `/** Doubles a number. */\nexport function ${name}(value: number): number {\n  return value * 2;\n}\n`;
+  const diff = diffProjects({ "src/math.ts": source("double") }, { "src/math.ts": source("twice") }, 1.01);
+
+  assert.deepEqual(
+    diff.symbols.map((change) => change.change).sort(),
+    ["added", "removed"]
+  );
+});
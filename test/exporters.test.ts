@@ -0,0 +1,34 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+import { renderDot, renderGraphMl } from "../src/exporters";
+import { CodeGraph } from "../src/types";
+import { buildVisualizationData } from "../src/visualizer";
+
+const graph: CodeGraph = {
+  root: "/project",
+  generatedAt: "",
+  files: [
+    {
+      path: "src/a.ts",
+      imports: [
+        { from: "./b", symbols: ["b"], resolved: "src/b.ts" },
+        { from: "./b", symbols: ["B"], kind: "type", resolved: "src/b.ts" },
+        { from: "react", symbols: ["useState"] },
+        { from: "react", symbols: ["useEffect"] },
+      ],
+      symbols: [],
+    },
+    { path: "src/b.ts", imports: [], symbols: [] },
+  ],
+};
+
+test("DOT and GraphML draw one import edge per file and target, like the HTML view", () => {
+  const dotEdges = renderDot(graph)
+    .split("\n")
+    .filter((line) => line.includes("label=imports"));
+  const graphMlEdges = renderGraphMl(graph).match(/<edge /g) ?? [];
+
+  assert.equal(buildVisualizationData(graph).links.length, 2);
+  assert.equal(dotEdges.length, 2);
+  assert.equal(graphMlEdges.length, 2);
+});
@@ -0,0 +1,15 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+
+/** Writes `files`, keyed by forward-slash paths, into a fresh temporary directory and returns its path. */
+export function writeProject(files: Record<string, string>): string {
+  const root = fs.mkdtempSync(path.join(os.tmpdir(), "ycg-test-"));
+  for (const [filePath, content] of Object.entries(files)) {
+    const target = path.join(root, ...filePath.split("/"));
+    fs.mkdirSync(path.dirname(target), { recursive: true });
+    fs.writeFileSync(target, content);
+  }
+
+  return root;
+}
@@ -0,0 +1,108 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+import { addFile, addImport, GraphMutationError, mergeGraphs } from "../src/mutations";
+import { CodeGraph, FileGraph } from "../src/types";
+
+function file(filePath: string, symbolName = "value"): FileGraph {
+  return { path: filePath, imports: [], symbols: [{ name: symbolName, kind: "variable", exported: true, location: { line: 1, column: 1 } }] };
+}
+
+function graph(...files: FileGraph[]): CodeGraph {
+  return { root: "/project", generatedAt: "", files };
+}
+
+test("addFile rejects a duplicate path and keeps code-unit order", () => {
+  const target = graph(file("src/b.ts"));
+  addFile(target, file("src/B.ts"));
+  addFile(target, file("src/a.ts"));
+
+  assert.deepEqual(
+    target.files.map((entry) => entry.path),
+    ["src/B.ts", "src/a.ts", "src/b.ts"]
+  );
+  assert.throws(() => addFile(target, file("src/a.ts")), GraphMutationError);
+});
+
+test("addImport rejects a target missing from the graph", () => {
+  const target = graph(file("src/a.ts"));
+
+  assert.throws(() => addImport(target, "src/a.ts", { from: "./gone", symbols: ["gone"], resolved: "src/gone.ts" }), GraphMutationError);
+  assert.deepEqual(target.files[0].imports, []);
+  assert.equal(target.files.length, 1);
+});
+
+test("addImport adds a stub for a missing target when asked to", () => {
+  const target = graph(file("src/a.ts"));
+  addImport(target, "src/a.ts", { from: "./trace", symbols: ["run"], resolved: "src/trace.ts" }, { stub: true });
+
+  assert.deepEqual(target.files[1], { path: "src/trace.ts", imports: [], symbols: [], stub: true });
+  assert.deepEqual(target.files[0].imports, [{ from: "./trace", symbols: ["run"], resolved: "src/trace.ts" }]);
+});
+
+test("addImport merges by module and kind and rejects conflicting targets", () => {
+  const target = graph(file("src/a.ts"), file("src/b.ts"), file("src/c.ts"));
+  addImport(target, "src/a.ts", { from: "./b", symbols: ["x"] });
+  addImport(target, "src/a.ts", { from: "./b", symbols: ["x", "y"], resolved: "src/b.ts" });
+  addImport(target, "src/a.ts", { from: "./b", symbols: ["T"], kind: "type", resolved: "src/b.ts" });
+
+  assert.deepEqual(target.files[0].imports, [
+    { from: "./b", symbols: ["x", "y"], resolved: "src/b.ts" },
+    { from: "./b", symbols: ["T"], kind: "type", resolved: "src/b.ts" },
+  ]);
+  assert.throws(() => addImport(target, "src/a.ts", { from: "./b", symbols: ["z"], resolved: "src/c.ts" }), GraphMutationError);
+});
+
+test("mergeGraphs with skip keeps the target's file", () => {
+  const target = graph(file("src/a.ts", "mine"));
+  mergeGraphs(target, graph(file("src/a.ts", "theirs"), file("src/b.ts")), "skip");
+
+  assert.deepEqual(
+    target.files.map((entry) => [entry.path, entry.symbols[0].name]),
+    [
+      ["src/a.ts", "mine"],
+      ["src/b.ts", "value"],
+    ]
+  );
+});
+
+test("mergeGraphs with overwrite takes the source's file", () => {
+  const target = graph(file("src/a.ts", "mine"));
+  mergeGraphs(target, graph(file("src/a.ts", "theirs")), "overwrite");
+
+  assert.equal(target.files[0].symbols[0].name, "theirs");
+});
+
+test("mergeGraphs with error leaves the target unchanged on a conflict", () => {
+  const target = graph(file("src/b.ts"));
+
+  assert.throws(() => mergeGraphs(target, graph(file("src/a.ts"), file("src/b.ts")), "error"), GraphMutationError);
+  assert.deepEqual(
+    target.files.map((entry) => entry.path),
+    ["src/b.ts"]
+  );
+});
+
+test("mergeGraphs merges common externals and packages", () => {
+  const target: CodeGraph = { ...graph(), commonExternals: [{ module: "react", references: 3 }], packages: [{ name: "web", path: "apps/web" }] };
+  const source: CodeGraph = {
+    ...graph(),
+    commonExternals: [
+      { module: "lodash", references: 4 },
+      { module: "react", references: 2 },
+    ],
+    packages: [
+      { name: "api", path: "apps/api" },
+      { name: "web-copy", path: "apps/web" },
+    ],
+  };
+  mergeGraphs(target, source);
+
+  assert.deepEqual(target.commonExternals, [
+    { module: "react", references: 5 },
+    { module: "lodash", references: 4 },
+  ]);
+  assert.deepEqual(target.packages, [
+    { name: "api", path: "apps/api" },
+    { name: "web", path: "apps/web" },
+  ]);
+});
@@ -0,0 +1,64 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+import { GRAPH_SCHEMA_VERSION, isNewerSchema, migrateGraph, UNVERSIONED_SCHEMA } from "../src/schema";
+import { deserializeGraph, readGraphDocument, serializeGraph } from "../src/yamlSerializer";
+
+const UNVERSIONED = `root: /project
+generatedAt: 2024-01-01T00:00:00.000Z
+files:
+  - path: src/a.ts
+    imports: []
+    symbols:
+      - name: a
+        kind: function
+        exported: true
+        location:
+          line: 1
+          column: 1
+`;
+
+test("graphs without a version are read as the first schema and migrated", () => {
+  const graph = deserializeGraph(UNVERSIONED);
+
+  assert.equal(graph.schemaVersion, GRAPH_SCHEMA_VERSION);
+  assert.equal(graph.files[0].symbols[0].name, "a");
+});
+
+test("migration stops at the requested version", () => {
+  const graph = migrateGraph({ root: "", generatedAt: "", files: [] }, UNVERSIONED_SCHEMA);
+
+  assert.equal(graph.schemaVersion, UNVERSIONED_SCHEMA);
+});
+
+test("graphs of a newer schema keep their version and unknown fields", () => {
+  const newer = `schemaVersion: ${GRAPH_SCHEMA_VERSION + 1}\nroot: /project\ngeneratedAt: ""\nfuture: true\nfiles: []\n`;
+  const graph = deserializeGraph(newer);
+
+  assert.ok(isNewerSchema(graph));
+  assert.match(serializeGraph(graph), new RegExp(`^schemaVersion: ${GRAPH_SCHEMA_VERSION + 1}$`, "m"));
+  assert.match(serializeGraph(graph), /^future: true$/m);
+});
+
+test("migrated graphs round-trip in short keys", () => {
+  const graph = deserializeGraph(UNVERSIONED);
+  const reread = deserializeGraph(serializeGraph(graph, { shortKeys: true }));
+
+  assert.deepEqual(reread.files, graph.files);
+  assert.equal(reread.schemaVersion, GRAPH_SCHEMA_VERSION);
+});
+
+test("the key form, metadata, and declared version are read from the document, not its text", () => {
+  const flow = readGraphDocument('{ "v": 1, "r": "/project", "t": "", "f": [] }\n');
+  assert.deepEqual([flow.shortKeys, flow.metadata, flow.schemaVersion], [true, true, 1]);
+  assert.equal(flow.graph.schemaVersion, GRAPH_SCHEMA_VERSION);
+
+  const bare = readGraphDocument(serializeGraph({ root: "/project", generatedAt: "", files: [] }, { metadata: false }));
+  assert.deepEqual([bare.shortKeys, bare.metadata, bare.schemaVersion], [false, false, GRAPH_SCHEMA_VERSION]);
+
+  const unversioned = readGraphDocument(UNVERSIONED);
+  assert.deepEqual([unversioned.shortKeys, unversioned.metadata, unversioned.schemaVersion], [false, true, UNVERSIONED_SCHEMA]);
+});
+
+test("a quoted schema version is rejected rather than guessed", () => {
+  assert.throws(() => readGraphDocument('schemaVersion: "2"\nfiles: []\n'), /schemaVersion must be a positive integer/);
+});
@@ -0,0 +1,29 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+import { buildGraph } from "../src/graph";
+import { computeTokenReport } from "../src/stats";
+import { writeProject } from "./helpers";
+
+const root = writeProject({
+  "src/a.ts": 'import { b } from "./b";\n/** Adds one. */\nexport function a(value: number): number {\n  return b(value) + 1;\n}\n',
+  "src/b.ts": "export function b(value: number): number {\n  return value;\n}\n",
+  "src/c.ts": 'import "./a";\nexport interface C {\n  name: string;\n}\nexport const c = 1;\n',
+});
+const graph = buildGraph(root);
+
+for (const [label, options] of [
+  ["long keys", {}],
+  ["short keys", { shortKeys: true }],
+  ["no metadata", { metadata: false }],
+  ["short keys without metadata", { shortKeys: true, metadata: false }],
+] as const) {
+  test(`file totals plus the envelope equal the total, ${label}`, () => {
+    const report = computeTokenReport(graph, options);
+    const files = report.topFiles.reduce((sum, file) => sum + file.tokens, 0);
+
+    assert.equal(report.topFiles.length, graph.files.length);
+    assert.ok(report.envelope >= 0, `envelope is ${report.envelope}`);
+    // Each figure is rounded on its own, so the sum may be off by one per file.
+    assert.ok(Math.abs(files + report.envelope - report.total) <= graph.files.length + 1, `${files} + ${report.envelope} != ${report.total}`);
+  });
+}